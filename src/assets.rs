@@ -1,4 +1,4 @@
-use crate::core::basic::Gua64Info;
+use crate::core::{basic::Gua64Info, error::CoreError};
 use anyhow::{Ok, anyhow};
 use gpui::AssetSource;
 use rust_embed::RustEmbed;
@@ -27,10 +27,8 @@ impl AssetSource for Assets {
 }
 
 /// 初始化资源
-pub fn init_gua64_info() -> Vec<Gua64Info> {
+pub fn init_gua64_info() -> Result<Vec<Gua64Info>, CoreError> {
     let gua64_json_str = include_str!("../assets/gua64.json");
 
-    let gua64_info: Vec<Gua64Info> = serde_json::from_str(gua64_json_str).unwrap();
-
-    gua64_info
+    serde_json::from_str(gua64_json_str).map_err(CoreError::from)
 }
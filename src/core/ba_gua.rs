@@ -1,11 +1,14 @@
 use std::fmt::Debug;
 
-use chrono::{DateTime, Local};
-use gpui::{Context, IntoElement, ParentElement, Render, Window, div};
-use gpui_component::h_flex;
+use chrono::{DateTime, Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
 
-use crate::core::models::{Gua8, Gua64, Gua64YaoIndex};
+use crate::core::da_yan::DaYan;
+use crate::core::error::CoreError;
+use crate::core::gan_zhi::DiZhi;
+use crate::core::models::{Gua8, Gua64, Gua64YaoIndex, LiuYaoType, ShuZi, ShuZiMode};
 use crate::core::utils::ichang_mod;
+use crate::core::yao_gua::CoinToss;
 
 /// 卦象计算器
 #[derive(Debug)]
@@ -17,120 +20,681 @@ impl BaGuaCalculator {
     /// * `shang_num` - 计算上卦的数字
     /// * `xia_num` - 计算下卦的数字
     /// * `bian_num` - 计算变爻的数字
-    pub fn calculate_from_two_numbers(shang_num: u16, xia_num: u16, bian_num: u16) -> GuaResult {
+    /// * `source` - 起卦方式以及原始输入
+    pub fn calculate_from_two_numbers(
+        shang_num: u64,
+        xia_num: u64,
+        bian_num: u64,
+        source: QiGuaSource,
+    ) -> Result<GuaResult, CoreError> {
         // 1. 将 num1 取余数
         // 计算上卦和下卦
         let shang_gua_num = ichang_mod(shang_num, 8);
         let xia_gua_num = ichang_mod(xia_num, 8);
 
         // 计算本卦
-        let shang_gua = Gua8::from_num(shang_gua_num);
-        let xia_gua = Gua8::from_num(xia_gua_num);
+        let shang_gua = Gua8::from_num(shang_gua_num)?;
+        let xia_gua = Gua8::from_num(xia_gua_num)?;
         let ben_gua = Gua64::new(shang_gua, xia_gua);
 
         // 余数即变爻的位置
         let bian_index = Gua64YaoIndex::from(bian_num);
 
-        // 变卦
-        let mut bian_gua = ben_gua.clone();
-        bian_gua.change(bian_index);
+        Ok(GuaResult::new(ben_gua, vec![bian_index], source))
+    }
 
-        // 互卦
-        let hu_gua = ben_gua.hu_gua();
+    /// 梅花易数数字起卦，按指定的公式计算变爻
+    ///
+    /// * `shang_num` - 计算上卦的数字，可以任意长
+    /// * `xia_num` - 计算下卦的数字，可以任意长
+    /// * `mode` - 由数字得到起卦数的方式
+    /// * `formula` - 变爻的计算公式，三数起卦时包含第三个数
+    pub fn calculate_from_numbers(
+        shang_num: ShuZi,
+        xia_num: ShuZi,
+        mode: ShuZiMode,
+        formula: DongYaoFormula,
+    ) -> Result<GuaResult, CoreError> {
+        let shang_value = shang_num.value(mode);
+        let xia_value = xia_num.value(mode);
+        let bian_value = formula.bian_num(shang_value, xia_value, mode);
+
+        let source = QiGuaSource::TwoNumber {
+            shang_num,
+            xia_num,
+            mode,
+            formula,
+        };
+
+        Self::calculate_from_two_numbers(shang_value, xia_value, bian_value, source)
+    }
+}
 
-        GuaResult::new(ben_gua, Some(bian_gua), hu_gua)
+/// 变爻公式的种类，不带时辰和第三个数，用于选择公式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DongYaoFormulaKind {
+    /// 上下两数之和
+    #[default]
+    Sum,
+    /// 上下两数之和加时辰数
+    SumWithShiChen,
+    /// 第三个数单独作为变爻数
+    Third,
+    /// 三数之和作为变爻数
+    SumOfThree,
+}
+
+impl DongYaoFormulaKind {
+    pub const ALL: [DongYaoFormulaKind; 4] = [
+        DongYaoFormulaKind::Sum,
+        DongYaoFormulaKind::SumWithShiChen,
+        DongYaoFormulaKind::Third,
+        DongYaoFormulaKind::SumOfThree,
+    ];
+
+    /// 公式名称
+    pub const fn name(&self) -> &'static str {
+        match self {
+            DongYaoFormulaKind::Sum => "两数之和",
+            DongYaoFormulaKind::SumWithShiChen => "两数之和加时辰",
+            DongYaoFormulaKind::Third => "第三数",
+            DongYaoFormulaKind::SumOfThree => "三数之和",
+        }
+    }
+
+    /// 是否需要第三个数
+    pub const fn is_san_shu(&self) -> bool {
+        matches!(
+            self,
+            DongYaoFormulaKind::Third | DongYaoFormulaKind::SumOfThree
+        )
+    }
+}
+
+/// 数字起卦时变爻的计算公式
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DongYaoFormula {
+    /// 上下两数之和
+    Sum,
+    /// 上下两数之和加时辰数，子时为 1
+    SumWithShiChen(DiZhi),
+    /// 三数起卦，第三个数单独作为变爻数
+    Third(ShuZi),
+    /// 三数起卦，三数之和作为变爻数
+    SumOfThree(ShuZi),
+}
+
+impl DongYaoFormula {
+    /// 计算变爻数
+    ///
+    /// * `shang_value` - 上卦的起卦数
+    /// * `xia_value` - 下卦的起卦数
+    /// * `mode` - 第三个数的取数方式，与上下卦相同
+    pub fn bian_num(&self, shang_value: u64, xia_value: u64, mode: ShuZiMode) -> u64 {
+        let sum = shang_value + xia_value;
+
+        match self {
+            DongYaoFormula::Sum => sum,
+            DongYaoFormula::SumWithShiChen(shi_chen) => sum + shi_chen.number() as u64,
+            DongYaoFormula::Third(third) => third.value(mode),
+            DongYaoFormula::SumOfThree(third) => sum + third.value(mode),
+        }
+    }
+
+    /// 公式的种类
+    pub const fn kind(&self) -> DongYaoFormulaKind {
+        match self {
+            DongYaoFormula::Sum => DongYaoFormulaKind::Sum,
+            DongYaoFormula::SumWithShiChen(_) => DongYaoFormulaKind::SumWithShiChen,
+            DongYaoFormula::Third(_) => DongYaoFormulaKind::Third,
+            DongYaoFormula::SumOfThree(_) => DongYaoFormulaKind::SumOfThree,
+        }
+    }
+
+    /// 公式名称
+    pub const fn name(&self) -> &'static str {
+        self.kind().name()
+    }
+
+    /// 是否需要第三个数
+    pub const fn is_san_shu(&self) -> bool {
+        self.kind().is_san_shu()
+    }
+
+    /// 如 `两数之和加时辰（午时）`、`三数之和（数字3：5）`
+    pub fn display(&self) -> String {
+        match self {
+            DongYaoFormula::Sum => self.name().to_string(),
+            DongYaoFormula::SumWithShiChen(shi_chen) => {
+                format!("{}（{}时）", self.name(), shi_chen)
+            }
+            DongYaoFormula::Third(third) | DongYaoFormula::SumOfThree(third) => {
+                format!("{}（数字3：{}）", self.name(), third)
+            }
+        }
+    }
+}
+
+/// 起卦方式以及原始输入
+///
+/// 用于审查和复现算卦结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QiGuaSource {
+    /// 数字起卦，包括两数和三数起卦
+    TwoNumber {
+        /// 计算上卦的数字
+        shang_num: ShuZi,
+        /// 计算下卦的数字
+        xia_num: ShuZi,
+        /// 取数方式
+        mode: ShuZiMode,
+        /// 变爻的计算公式
+        formula: DongYaoFormula,
+    },
+    /// 时间起卦
+    Time {
+        /// 选择的公历时间
+        date: NaiveDateTime,
+        /// 农历日期
+        lunar_date: String,
+        /// 年支
+        year_branch: DiZhi,
+        /// 农历月
+        lunar_month: u8,
+        /// 农历日
+        lunar_day: u8,
+        /// 时辰
+        shi_chen: DiZhi,
+    },
+    /// 六爻起卦，从初爻到上爻
+    LiuYao([LiuYaoType; 6]),
+    /// 大衍筮法，保留十八变的过程
+    DaYan(DaYan),
+    /// 摇卦，六次投掷的铜钱，从初爻到上爻
+    YaoGua([CoinToss; 6]),
+}
+
+impl QiGuaSource {
+    /// 起卦方式名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            QiGuaSource::TwoNumber { formula, .. } => match formula.is_san_shu() {
+                true => "三个数字",
+                false => "两个数字",
+            },
+            QiGuaSource::Time { .. } => "时间",
+            QiGuaSource::LiuYao(_) => "六爻",
+            QiGuaSource::DaYan(_) => "大衍筮法",
+            QiGuaSource::YaoGua(_) => "摇卦",
+        }
+    }
+
+    /// 逐爻起卦时六个爻的类型，从初爻到上爻
+    pub fn liu_yao_list(&self) -> Option<[LiuYaoType; 6]> {
+        match self {
+            QiGuaSource::LiuYao(yao_list) => Some(*yao_list),
+            QiGuaSource::DaYan(da_yan) => Some(da_yan.liu_yao_list()),
+            QiGuaSource::YaoGua(toss_list) => Some(toss_list.map(|toss| toss.liu_yao_type())),
+            _ => None,
+        }
+    }
+
+    /// 原始输入
+    pub fn display(&self) -> String {
+        match self {
+            QiGuaSource::TwoNumber {
+                shang_num,
+                xia_num,
+                mode,
+                formula,
+            } => {
+                format!(
+                    "数字1：{shang_num}，数字2：{xia_num}，取数：{mode}，变爻：{}",
+                    formula.display()
+                )
+            }
+            QiGuaSource::Time {
+                date,
+                lunar_date,
+                shi_chen,
+                ..
+            } => {
+                format!(
+                    "公历：{}，农历：{}，时辰：{}时",
+                    date.format("%Y-%m-%d %H 时"),
+                    lunar_date,
+                    shi_chen
+                )
+            }
+            QiGuaSource::LiuYao(yao_list) => yao_list
+                .iter()
+                .map(|yao| yao.to_string())
+                .collect::<Vec<_>>()
+                .join("，"),
+            QiGuaSource::DaYan(da_yan) => da_yan
+                .yao_list
+                .iter()
+                .map(|yao| format!("{}（{}）", yao.number(), yao.name()))
+                .collect::<Vec<_>>()
+                .join("，"),
+            QiGuaSource::YaoGua(toss_list) => toss_list
+                .iter()
+                .map(|toss| format!("{}（{}）", toss.display(), toss.liu_yao_type()))
+                .collect::<Vec<_>>()
+                .join("，"),
+        }
+    }
+}
+
+/// 由一个卦衍生出的卦
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuaFamily {
+    /// 互卦
+    pub hu_gua: Gua64,
+    /// 错卦
+    pub cuo_gua: Gua64,
+    /// 综卦
+    pub zong_gua: Gua64,
+    /// 交卦
+    pub jiao_gua: Gua64,
+}
+
+impl GuaFamily {
+    pub const fn new(gua: &Gua64) -> Self {
+        Self {
+            hu_gua: gua.hu_gua(),
+            cuo_gua: gua.cuo_gua(),
+            zong_gua: gua.zong_gua(),
+            jiao_gua: gua.jiao_gua(),
+        }
+    }
+
+    /// 带名称的衍生卦列表，依次为互、错、综、交
+    pub fn list(&self) -> [(&'static str, &Gua64); 4] {
+        [
+            ("互卦", &self.hu_gua),
+            ("错卦", &self.cuo_gua),
+            ("综卦", &self.zong_gua),
+            ("交卦", &self.jiao_gua),
+        ]
+    }
+
+    /// 如 `互：剥，错：鼎，综：蒙，交：解`
+    pub fn display(&self) -> String {
+        self.list()
+            .iter()
+            .map(|(name, gua)| format!("{}：{}", name.trim_end_matches('卦'), gua.name()))
+            .collect::<Vec<_>>()
+            .join("，")
     }
 }
 
 /// 算卦结果
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GuaResult {
     /// 本卦
     pub ben_gua: Gua64,
-    /// 变卦，有可能不存在，即和本卦相同
-    pub bian_gua: Option<Gua64>,
+    /// 动爻，从下往上排列，变卦由此得出
+    pub dong_yao: Vec<Gua64YaoIndex>,
     /// 互挂
     pub hu_gua: Gua64,
     /// 算卦时间
     pub date: DateTime<Local>,
+    /// 起卦方式以及原始输入
+    pub source: QiGuaSource,
 }
 
 impl GuaResult {
-    pub fn new(ben_gua: Gua64, bian_gua: Option<Gua64>, hu_gua: Gua64) -> Self {
+    /// * `ben_gua` - 本卦
+    /// * `dong_yao` - 动爻，顺序和重复会被整理
+    /// * `source` - 起卦方式以及原始输入
+    pub fn new(ben_gua: Gua64, mut dong_yao: Vec<Gua64YaoIndex>, source: QiGuaSource) -> Self {
         let date = Local::now();
+
+        dong_yao.sort();
+        dong_yao.dedup();
+
+        // 互卦
+        let hu_gua = ben_gua.hu_gua();
+
         GuaResult {
             date,
             ben_gua,
-            bian_gua,
+            dong_yao,
             hu_gua,
+            source,
         }
     }
 
-    pub fn display(&self) -> String {
-        let ben_gua = self.ben_gua.name();
-        let bian_gua = match &self.bian_gua {
-            None => "无".into(),
-            Some(gua) => gua.name(),
-        };
-        let hu_gua = self.hu_gua.name();
+    /// 逐爻起卦的结果，老阴、老阳为动爻
+    ///
+    /// * `liu_yao_list` - 六个爻的类型，从初爻到上爻
+    /// * `source` - 起卦方式以及原始输入
+    pub fn from_liu_yao(liu_yao_list: [LiuYaoType; 6], source: QiGuaSource) -> Self {
+        let [first, second, third, fourth, fifth, sixth] = liu_yao_list.map(LiuYaoType::yao);
+        let ben_gua = Gua64::new(
+            Gua8::new(fourth, fifth, sixth),
+            Gua8::new(first, second, third),
+        );
 
-        let parsed_date = self
-            .date
-            .format("%Y 年 %m 月 %d 日 %H 时 %M 分")
-            .to_string();
+        let dong_yao = liu_yao_list
+            .into_iter()
+            .zip(Gua64YaoIndex::ALL)
+            .filter_map(|(yao, index)| yao.is_dong().then_some(index))
+            .collect();
 
-        format!(
-            "本卦：{}\n变卦：{}\n互卦：{}\n算卦时间：{}",
-            ben_gua, bian_gua, hu_gua, parsed_date
-        )
+        Self::new(ben_gua, dong_yao, source)
     }
 
-    pub fn ben_gua(&mut self) -> impl IntoElement {
-        let gua = self.ben_gua.clone();
+    /// 变卦，没有动爻时不存在
+    pub fn bian_gua(&self) -> Option<Gua64> {
+        match self.dong_yao.is_empty() {
+            true => None,
+            false => Some(self.ben_gua.changed(&self.dong_yao)),
+        }
+    }
 
-        div()
-            .child("本卦")
-            .child(gua.name())
-            .children(gua.into_iter().map(|yao| div().child(yao.name())))
+    /// 本卦的衍生卦
+    pub const fn ben_gua_family(&self) -> GuaFamily {
+        GuaFamily::new(&self.ben_gua)
     }
 
-    pub fn bian_gua(&mut self) -> impl IntoElement {
-        let gua = self.bian_gua.clone();
+    /// 变卦的衍生卦，没有变卦时不存在
+    pub fn bian_gua_family(&self) -> Option<GuaFamily> {
+        self.bian_gua().map(|gua| GuaFamily::new(&gua))
+    }
 
-        if let Some(gua) = gua {
-            div()
-                .child("变卦")
-                .child(gua.name())
-                .children(gua.into_iter().map(|yao| div().child(yao.name())))
-        } else {
-            div().child("无变卦")
+    /// 动爻名称，如 `三爻、四爻`
+    pub fn dong_yao_names(&self) -> String {
+        match self.dong_yao.is_empty() {
+            true => "无".into(),
+            false => self
+                .dong_yao
+                .iter()
+                .map(|index| index.name())
+                .collect::<Vec<_>>()
+                .join("、"),
         }
     }
-}
 
-impl Render for GuaResult {
-    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
-        h_flex().child(self.ben_gua()).child(self.bian_gua())
+    pub fn display(&self) -> String {
+        let ben_gua = self.ben_gua.full_name();
+        let bian_gua = match self.bian_gua() {
+            None => "无".to_string(),
+            Some(gua) => gua.full_name(),
+        };
+        let hu_gua = self.hu_gua.full_name();
+
+        let bian_gua_family = match self.bian_gua_family() {
+            None => "无".to_string(),
+            Some(family) => family.display(),
+        };
+
+        let parsed_date = self
+            .date
+            .format("%Y 年 %m 月 %d 日 %H 时 %M 分")
+            .to_string();
+
+        format!(
+            "起卦方式：{}\n原始输入：{}\n本卦：{}\n动爻：{}\n变卦：{}\n互卦：{}\n本卦衍生：{}\n变卦衍生：{}\n算卦时间：{}",
+            self.source.name(),
+            self.source.display(),
+            ben_gua,
+            self.dong_yao_names(),
+            bian_gua,
+            hu_gua,
+            self.ben_gua_family().display(),
+            bian_gua_family,
+            parsed_date
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::core::models::Gua64;
+    use crate::core::{
+        gan_zhi::DiZhi,
+        models::{Gua64, Gua64YaoIndex, LiuYaoType, ShuZi, ShuZiMode},
+    };
 
-    use super::BaGuaCalculator;
+    use super::{
+        BaGuaCalculator, DongYaoFormula, DongYaoFormulaKind, GuaFamily, GuaResult, QiGuaSource,
+    };
 
     #[test]
     /// 测试 calculate_from_two_numbers
     fn test_calculate_from_two_numbers() {
-        let r1 = BaGuaCalculator::calculate_from_two_numbers(128, 33, 128 + 33);
+        let source = QiGuaSource::TwoNumber {
+            shang_num: ShuZi::from(128),
+            xia_num: ShuZi::from(33),
+            mode: ShuZiMode::取余,
+            formula: DongYaoFormula::Sum,
+        };
+        let r1 =
+            BaGuaCalculator::calculate_from_two_numbers(128, 33, 128 + 33, source.clone()).unwrap();
         assert_eq!(r1.ben_gua, Gua64::泰);
-        assert_eq!(r1.bian_gua.unwrap(), Gua64::需);
+        assert_eq!(r1.dong_yao, vec![Gua64YaoIndex::Fifth]);
+        assert_eq!(r1.bian_gua().unwrap(), Gua64::需);
         assert_eq!(r1.hu_gua, Gua64::归妹);
+        assert_eq!(r1.source, source);
 
-        let r2 = BaGuaCalculator::calculate_from_two_numbers(63, 49, 63 + 49);
+        let source = QiGuaSource::TwoNumber {
+            shang_num: ShuZi::from(63),
+            xia_num: ShuZi::from(49),
+            mode: ShuZiMode::取余,
+            formula: DongYaoFormula::Sum,
+        };
+        let r2 = BaGuaCalculator::calculate_from_two_numbers(63, 49, 63 + 49, source).unwrap();
         assert_eq!(r2.ben_gua, Gua64::大畜);
-        assert_eq!(r2.bian_gua.unwrap(), Gua64::大有);
+        assert_eq!(r2.dong_yao, vec![Gua64YaoIndex::Fourth]);
+        assert_eq!(r2.bian_gua().unwrap(), Gua64::大有);
         assert_eq!(r2.hu_gua, Gua64::归妹);
     }
+
+    #[test]
+    /// 测试各种变爻公式
+    fn test_calculate_from_numbers() {
+        let shu_zi = |text: &str| text.parse::<ShuZi>().unwrap();
+        let calc = |formula| {
+            BaGuaCalculator::calculate_from_numbers(
+                shu_zi("128"),
+                shu_zi("33"),
+                ShuZiMode::取余,
+                formula,
+            )
+            .unwrap()
+        };
+
+        // 两数之和，与 calculate_from_two_numbers 一致
+        let r1 = calc(DongYaoFormula::Sum);
+        assert_eq!(r1.ben_gua, Gua64::泰);
+        assert_eq!(r1.dong_yao, vec![Gua64YaoIndex::Fifth]);
+        assert_eq!(r1.source.name(), "两个数字");
+
+        // 161 + 午时 7 = 168，除尽为上爻
+        let formula = DongYaoFormula::SumWithShiChen(DiZhi::午);
+        assert_eq!(formula.bian_num(128, 33, ShuZiMode::取余), 168);
+        let r2 = calc(formula);
+        assert_eq!(r2.ben_gua, Gua64::泰);
+        assert_eq!(r2.dong_yao, vec![Gua64YaoIndex::Sixth]);
+        assert_eq!(r2.bian_gua().unwrap(), Gua64::大畜);
+        assert_eq!(
+            r2.source.display(),
+            "数字1：128，数字2：33，取数：取余，变爻：两数之和加时辰（午时）"
+        );
+
+        // 第三数 3，为三爻
+        let r3 = calc(DongYaoFormula::Third(shu_zi("3")));
+        assert_eq!(r3.dong_yao, vec![Gua64YaoIndex::Third]);
+        assert_eq!(r3.bian_gua().unwrap(), Gua64::临);
+        assert_eq!(r3.source.name(), "三个数字");
+
+        // 三数之和 128 + 33 + 3 = 164，为二爻
+        let r4 = calc(DongYaoFormula::SumOfThree(shu_zi("3")));
+        assert_eq!(r4.dong_yao, vec![Gua64YaoIndex::Second]);
+        assert_eq!(r4.bian_gua().unwrap(), Gua64::明夷);
+        assert_eq!(
+            r4.source.display(),
+            "数字1：128，数字2：33，取数：取余，变爻：三数之和（数字3：3）"
+        );
+    }
+
+    #[test]
+    /// 测试长数字的两种取数方式
+    fn test_calculate_from_long_numbers() {
+        let shang_num: ShuZi = "138-0013-8000".parse().unwrap();
+        let xia_num: ShuZi = "13912345678".parse().unwrap();
+
+        // 取余：上 8 坤，下 6 坎，两数之和除以六余四
+        let r1 = BaGuaCalculator::calculate_from_numbers(
+            shang_num.clone(),
+            xia_num.clone(),
+            ShuZiMode::取余,
+            DongYaoFormula::Sum,
+        )
+        .unwrap();
+        assert_eq!(r1.ben_gua, Gua64::师);
+        assert_eq!(r1.dong_yao, vec![Gua64YaoIndex::Fourth]);
+        assert_eq!(r1.bian_gua().unwrap(), Gua64::解);
+
+        // 数字和：24 为坤，49 为乾，24 + 49 = 73 为初爻
+        let r2 = BaGuaCalculator::calculate_from_numbers(
+            shang_num,
+            xia_num,
+            ShuZiMode::数字和,
+            DongYaoFormula::Sum,
+        )
+        .unwrap();
+        assert_eq!(r2.ben_gua, Gua64::泰);
+        assert_eq!(r2.dong_yao, vec![Gua64YaoIndex::First]);
+        assert_eq!(r2.bian_gua().unwrap(), Gua64::升);
+
+        // 超出 u64 的数字，与取余后的结果一致：10^30 - 1 除以 8 余 7，除以 6 余 3
+        let long: ShuZi = "9".repeat(30).parse().unwrap();
+        let r3 = BaGuaCalculator::calculate_from_numbers(
+            long.clone(),
+            long.clone(),
+            ShuZiMode::取余,
+            DongYaoFormula::SumOfThree(long),
+        )
+        .unwrap();
+        assert_eq!(r3.ben_gua, Gua64::艮);
+        assert_eq!(r3.dong_yao, vec![Gua64YaoIndex::Third]);
+    }
+
+    #[test]
+    /// 测试由动爻得出变卦
+    fn test_bian_gua_from_dong_yao() {
+        let source = QiGuaSource::TwoNumber {
+            shang_num: ShuZi::from(0),
+            xia_num: ShuZi::from(0),
+            mode: ShuZiMode::取余,
+            formula: DongYaoFormula::Sum,
+        };
+
+        // 没有动爻，则没有变卦
+        let r1 = GuaResult::new(Gua64::乾, vec![], source.clone());
+        assert_eq!(r1.bian_gua(), None);
+
+        // 多个动爻，会按从下往上的顺序整理并去重
+        let r2 = GuaResult::new(
+            Gua64::乾,
+            vec![
+                Gua64YaoIndex::Sixth,
+                Gua64YaoIndex::First,
+                Gua64YaoIndex::Sixth,
+            ],
+            source,
+        );
+        assert_eq!(
+            r2.dong_yao,
+            vec![Gua64YaoIndex::First, Gua64YaoIndex::Sixth]
+        );
+        assert_eq!(r2.bian_gua().unwrap(), Gua64::大过);
+    }
+
+    #[test]
+    /// 公式的种类与公式一一对应，后两种需要第三个数
+    fn test_formula_kind() {
+        let formulas = [
+            DongYaoFormula::Sum,
+            DongYaoFormula::SumWithShiChen(DiZhi::午),
+            DongYaoFormula::Third(ShuZi::from(3)),
+            DongYaoFormula::SumOfThree(ShuZi::from(3)),
+        ];
+
+        let kinds: Vec<_> = formulas.iter().map(|formula| formula.kind()).collect();
+        assert_eq!(kinds, DongYaoFormulaKind::ALL);
+
+        let san_shu: Vec<_> = kinds.iter().map(|kind| kind.is_san_shu()).collect();
+        assert_eq!(san_shu, [false, false, true, true]);
+    }
+
+    #[test]
+    /// 测试由六个爻得出本卦和动爻
+    fn test_from_liu_yao() {
+        let liu_yao_list = [
+            LiuYaoType::阳,
+            LiuYaoType::阴,
+            LiuYaoType::动阳,
+            LiuYaoType::动阴,
+            LiuYaoType::阳,
+            LiuYaoType::阳,
+        ];
+        let result = GuaResult::from_liu_yao(liu_yao_list, QiGuaSource::LiuYao(liu_yao_list));
+
+        assert_eq!(result.ben_gua, Gua64::家人);
+        assert_eq!(
+            result.dong_yao,
+            vec![Gua64YaoIndex::Third, Gua64YaoIndex::Fourth]
+        );
+        assert_eq!(result.bian_gua().unwrap(), Gua64::无妄);
+        assert_eq!(result.hu_gua, Gua64::未济);
+
+        // 没有动爻时没有变卦
+        let liu_yao_list = liu_yao_list.map(|liu_yao| match liu_yao.yao().is_yang() {
+            true => LiuYaoType::阳,
+            false => LiuYaoType::阴,
+        });
+        let result = GuaResult::from_liu_yao(liu_yao_list, QiGuaSource::LiuYao(liu_yao_list));
+
+        assert_eq!(result.ben_gua, Gua64::家人);
+        assert!(result.dong_yao.is_empty());
+        assert!(result.bian_gua().is_none());
+        assert_eq!(result.hu_gua, Gua64::未济);
+    }
+
+    #[test]
+    /// 测试本卦和变卦的衍生卦
+    fn test_gua_family() {
+        let source = QiGuaSource::TwoNumber {
+            shang_num: ShuZi::from(0),
+            xia_num: ShuZi::from(0),
+            mode: ShuZiMode::取余,
+            formula: DongYaoFormula::Sum,
+        };
+
+        let r1 = GuaResult::new(Gua64::屯, vec![], source.clone());
+        assert_eq!(
+            r1.ben_gua_family(),
+            GuaFamily {
+                hu_gua: Gua64::剥,
+                cuo_gua: Gua64::鼎,
+                zong_gua: Gua64::蒙,
+                jiao_gua: Gua64::解,
+            }
+        );
+        assert_eq!(
+            r1.ben_gua_family().display(),
+            "互：剥，错：鼎，综：蒙，交：解"
+        );
+        assert_eq!(r1.bian_gua_family(), None);
+
+        // 屯变初爻为比
+        let r2 = GuaResult::new(Gua64::屯, vec![Gua64YaoIndex::First], source);
+        let family = r2.bian_gua_family().unwrap();
+        assert_eq!(family.cuo_gua, Gua64::大有);
+        assert_eq!(family.zong_gua, Gua64::师);
+        assert_eq!(family.jiao_gua, Gua64::师);
+    }
 }
@@ -2,6 +2,8 @@ use gpui::{Context, IntoElement, Render, Window};
 use gpui_component::description_list::{DescriptionItem, DescriptionList};
 use serde::{Deserialize, Serialize};
 
+use crate::core::models::Gua64;
+
 /// 64卦信息结构体
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Gua64Info {
@@ -15,6 +17,15 @@ pub struct Gua64Info {
     pub symbol: String,
 }
 
+impl Gua64Info {
+    /// 从列表中找到卦象对应的信息
+    pub fn find<'a>(list: &'a [Gua64Info], gua: &Gua64) -> Option<&'a Gua64Info> {
+        let id = gua.id();
+
+        list.iter().find(|info| info.id == id)
+    }
+}
+
 impl Render for Gua64Info {
     fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
         let id = self.id.clone();
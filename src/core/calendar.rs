@@ -0,0 +1,158 @@
+//! 干支历：年柱、月柱、日柱、时柱
+//!
+//! * 年以立春为界，月以节气中的“节”为界，不看农历年月
+//! * 日以子初（23 时）换日，23 时之后按次日的日柱计算
+
+use std::fmt::Display;
+
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+
+use crate::core::{
+    gan_zhi::{DiZhi, GanZhi, TianGan},
+    jie_qi::JieQi,
+};
+
+/// 四柱
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SiZhu {
+    /// 年柱
+    pub nian: GanZhi,
+    /// 月柱，即月建
+    pub yue: GanZhi,
+    /// 日柱，即日辰
+    pub ri: GanZhi,
+    /// 时柱
+    pub shi: GanZhi,
+}
+
+impl SiZhu {
+    /// 计算任意时间的四柱
+    ///
+    /// 日柱和时柱按该时区的钟表时间计算，月柱和年柱按太阳实际位置计算
+    pub fn new<Tz: TimeZone>(datetime: &DateTime<Tz>) -> Self {
+        let local = datetime.naive_local();
+
+        // 从立春开始的月序号，寅月为 0
+        let yue_index = yue_index(JieQi::from_time(datetime));
+
+        // 一、二月还在丑月或子月，说明没到立春，仍算上一年
+        let year = match local.month() <= 2 && yue_index >= 10 {
+            true => local.year() - 1,
+            false => local.year(),
+        };
+
+        // 公元 4 年为甲子年
+        let nian = GanZhi::from_index((year - 4).rem_euclid(60) as usize);
+
+        // 五虎遁：甲己之年丙作首，乙庚之岁戊为头，丙辛必定寻庚起，丁壬壬位顺行流，戊癸何方发，甲寅之上好追求
+        let yue = GanZhi::new(
+            TianGan::from_index(nian.gan.index() * 2 + 2 + yue_index),
+            DiZhi::from_index(yue_index + 2),
+        );
+
+        // 23 时之后为次日子时
+        let ri_date = match local.hour() >= 23 {
+            true => local.date() + Duration::days(1),
+            false => local.date(),
+        };
+        let ri = GanZhi::from_day(ri_date);
+
+        // 五鼠遁：甲己还加甲，乙庚丙作初，丙辛从戊起，丁壬庚子居，戊癸何方发，壬子是真途
+        let shi_zhi = DiZhi::from_hour(local.hour());
+        let shi = GanZhi::new(
+            TianGan::from_index(ri.gan.index() * 2 + shi_zhi.index()),
+            shi_zhi,
+        );
+
+        Self { nian, yue, ri, shi }
+    }
+}
+
+impl Display for SiZhu {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}年 {}月 {}日 {}时",
+            self.nian, self.yue, self.ri, self.shi
+        )
+    }
+}
+
+/// 根据节气得出月序号，寅月（立春）为 0
+fn yue_index(jie_qi: JieQi) -> usize {
+    // 小寒为 0，立春为 2，每个月包含一节一气
+    (jie_qi.index() + 22) % 24 / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+
+    use super::SiZhu;
+
+    /// 北京时间
+    fn beijing(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<FixedOffset> {
+        FixedOffset::east_opt(8 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(y, m, d, h, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    /// 测试四柱
+    fn test_si_zhu() {
+        let si_zhu = SiZhu::new(&beijing(2000, 1, 1, 12, 0));
+        assert_eq!(si_zhu.to_string(), "己卯年 丙子月 戊午日 戊午时");
+
+        // 十二月的子月仍属当年
+        let si_zhu = SiZhu::new(&beijing(2023, 12, 25, 9, 0));
+        assert_eq!(si_zhu.nian.to_string(), "癸卯");
+        assert_eq!(si_zhu.yue.to_string(), "甲子");
+
+        // 2024 年小寒为 1 月 6 日 4 时 49 分
+        assert_eq!(
+            SiZhu::new(&beijing(2024, 1, 6, 4, 40)).yue.to_string(),
+            "甲子"
+        );
+        assert_eq!(
+            SiZhu::new(&beijing(2024, 1, 6, 5, 0)).yue.to_string(),
+            "乙丑"
+        );
+    }
+
+    #[test]
+    /// 年和月以立春为界：2024 年立春为 2 月 4 日 16 时 27 分
+    fn test_li_chun() {
+        let si_zhu = SiZhu::new(&beijing(2024, 2, 4, 16, 20));
+        assert_eq!(si_zhu.nian.to_string(), "癸卯");
+        assert_eq!(si_zhu.yue.to_string(), "乙丑");
+
+        let si_zhu = SiZhu::new(&beijing(2024, 2, 4, 16, 35));
+        assert_eq!(si_zhu.nian.to_string(), "甲辰");
+        assert_eq!(si_zhu.yue.to_string(), "丙寅");
+
+        // 同一时刻换成世界时，年月不变，日时按当地时间
+        let utc = Utc.with_ymd_and_hms(2024, 2, 4, 8, 35, 0).unwrap();
+        let si_zhu = SiZhu::new(&utc);
+        assert_eq!(si_zhu.nian.to_string(), "甲辰");
+        assert_eq!(si_zhu.yue.to_string(), "丙寅");
+        assert_eq!(si_zhu.shi.zhi.to_string(), "辰");
+    }
+
+    #[test]
+    /// 23 时之后换日
+    fn test_zi_shi() {
+        let si_zhu = SiZhu::new(&beijing(2000, 1, 1, 22, 59));
+        assert_eq!(si_zhu.ri.to_string(), "戊午");
+        assert_eq!(si_zhu.shi.to_string(), "癸亥");
+
+        let si_zhu = SiZhu::new(&beijing(2000, 1, 1, 23, 30));
+        assert_eq!(si_zhu.ri.to_string(), "己未");
+        assert_eq!(si_zhu.shi.to_string(), "甲子");
+
+        let si_zhu = SiZhu::new(&beijing(2000, 1, 2, 0, 30));
+        assert_eq!(si_zhu.ri.to_string(), "己未");
+        assert_eq!(si_zhu.shi.to_string(), "甲子");
+    }
+}
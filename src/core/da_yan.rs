@@ -0,0 +1,264 @@
+//! 大衍筮法
+//!
+//! 大衍之数五十，其用四十有九。分而为二以象两，挂一以象三，揲之以四以象四时，归奇于扐以象闰。
+//!
+//! * 每一变：分二、挂一、揲四、归奇，去掉归奇的蓍草，剩下的进入下一变
+//! * 三变成一爻，剩余蓍草除以四，得六、七、八、九
+//! * 十八变成一卦
+//!
+//! 老阴、少阳、少阴、老阳的概率分别为 1/16、5/16、7/16、3/16
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::core::models::LiuYaoType;
+
+/// 大衍之数，取用的蓍草数
+pub const DA_YAN_ZHI_SHU: u8 = 49;
+
+/// 一变
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaYanBian {
+    /// 本变开始时的蓍草数
+    pub total: u8,
+    /// 分二后左手（象天）的蓍草数
+    pub left: u8,
+    /// 分二后右手（象地）的蓍草数，尚未挂一
+    pub right: u8,
+}
+
+impl DaYanBian {
+    /// * `total` - 本变开始时的蓍草数
+    /// * `left` - 分二时左手的蓍草数，两边至少要留下一根，右手还要挂一
+    pub const fn new(total: u8, left: u8) -> Self {
+        Self {
+            total,
+            left,
+            right: total - left,
+        }
+    }
+
+    /// 随机分二
+    ///
+    /// 左手的数量在 4 的整数倍个取值中均匀选取，使揲四的余数等可能，与古法的概率一致
+    pub fn random(total: u8) -> Self {
+        let max = (total - 2) / 4 * 4;
+        let left = rand::rng().random_range(1..=max);
+
+        Self::new(total, left)
+    }
+
+    /// 挂一，从右手取一根挂于左手小指间
+    pub const fn gua_yi(&self) -> u8 {
+        1
+    }
+
+    /// 揲四后左手的余数，除尽时余四
+    pub const fn left_remainder(&self) -> u8 {
+        remainder(self.left)
+    }
+
+    /// 揲四后右手的余数，除尽时余四
+    pub const fn right_remainder(&self) -> u8 {
+        remainder(self.right - self.gua_yi())
+    }
+
+    /// 归奇，挂一和两手的余数合在一起，第一变为五或九，之后为四或八
+    pub const fn gui_qi(&self) -> u8 {
+        self.gua_yi() + self.left_remainder() + self.right_remainder()
+    }
+
+    /// 去掉归奇后剩下的蓍草数
+    pub const fn rest(&self) -> u8 {
+        self.total - self.gui_qi()
+    }
+
+    /// 每一步的说明
+    pub fn steps(&self) -> [String; 4] {
+        [
+            format!(
+                "分二：{} 分为左 {}、右 {}",
+                self.total, self.left, self.right
+            ),
+            format!(
+                "挂一：右手取 {}，余 {}",
+                self.gua_yi(),
+                self.right - self.gua_yi()
+            ),
+            format!(
+                "揲四：左余 {}，右余 {}",
+                self.left_remainder(),
+                self.right_remainder()
+            ),
+            format!("归奇：{}，剩余 {}", self.gui_qi(), self.rest()),
+        ]
+    }
+}
+
+/// 三变成一爻
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaYanYao {
+    /// 三变的过程
+    pub bian_list: [DaYanBian; 3],
+}
+
+impl DaYanYao {
+    /// 根据三变时左手的蓍草数推演
+    pub const fn new(left_list: [u8; 3]) -> Self {
+        let first = DaYanBian::new(DA_YAN_ZHI_SHU, left_list[0]);
+        let second = DaYanBian::new(first.rest(), left_list[1]);
+        let third = DaYanBian::new(second.rest(), left_list[2]);
+
+        Self {
+            bian_list: [first, second, third],
+        }
+    }
+
+    /// 随机推演三变
+    pub fn random() -> Self {
+        let first = DaYanBian::random(DA_YAN_ZHI_SHU);
+        let second = DaYanBian::random(first.rest());
+        let third = DaYanBian::random(second.rest());
+
+        Self {
+            bian_list: [first, second, third],
+        }
+    }
+
+    /// 三变后剩余的蓍草除以四，得六、七、八、九
+    pub const fn number(&self) -> u8 {
+        self.bian_list[2].rest() / 4
+    }
+
+    /// 六为老阴，七为少阳，八为少阴，九为老阳，老阴老阳为动爻
+    pub const fn liu_yao_type(&self) -> LiuYaoType {
+        LiuYaoType::from_number(self.number()).expect("三变之后只会是六、七、八、九")
+    }
+
+    /// 爻的名称
+    pub const fn name(&self) -> &'static str {
+        match self.liu_yao_type() {
+            LiuYaoType::动阴 => "老阴",
+            LiuYaoType::阳 => "少阳",
+            LiuYaoType::阴 => "少阴",
+            LiuYaoType::动阳 => "老阳",
+        }
+    }
+}
+
+/// 十八变成一卦，从初爻到上爻
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaYan {
+    pub yao_list: [DaYanYao; 6],
+}
+
+impl DaYan {
+    /// 随机推演十八变
+    pub fn random() -> Self {
+        Self {
+            yao_list: std::array::from_fn(|_| DaYanYao::random()),
+        }
+    }
+
+    /// 六个爻的类型，从初爻到上爻
+    pub fn liu_yao_list(&self) -> [LiuYaoType; 6] {
+        self.yao_list.map(|yao| yao.liu_yao_type())
+    }
+}
+
+/// 揲四的余数，除尽时余四
+const fn remainder(num: u8) -> u8 {
+    match num % 4 {
+        0 => 4,
+        rest => rest,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::LiuYaoType;
+
+    use super::{DA_YAN_ZHI_SHU, DaYan, DaYanBian, DaYanYao};
+
+    #[test]
+    /// 测试一变的过程
+    fn test_bian() {
+        let bian = DaYanBian::new(49, 20);
+        assert_eq!(bian.right, 29);
+        assert_eq!(bian.left_remainder(), 4);
+        assert_eq!(bian.right_remainder(), 4);
+        assert_eq!(bian.gui_qi(), 9);
+        assert_eq!(bian.rest(), 40);
+        assert_eq!(bian.steps()[3], "归奇：9，剩余 40");
+
+        // 第一变为五或九，之后为四或八
+        for left in 1..=47 {
+            assert!([5, 9].contains(&DaYanBian::new(49, left).gui_qi()));
+        }
+        for total in [44, 40, 36, 32] {
+            for left in 1..=total - 2 {
+                assert!([4, 8].contains(&DaYanBian::new(total, left).gui_qi()));
+            }
+        }
+    }
+
+    #[test]
+    /// 测试三变成爻
+    fn test_yao() {
+        // 49 - 9 - 8 - 8 = 24，老阴
+        let yao = DaYanYao::new([20, 20, 16]);
+        assert_eq!(yao.number(), 6);
+        assert_eq!(yao.liu_yao_type(), LiuYaoType::动阴);
+
+        // 49 - 5 - 4 - 4 = 36，老阳
+        let yao = DaYanYao::new([1, 1, 1]);
+        assert_eq!(yao.number(), 9);
+        assert_eq!(yao.name(), "老阳");
+    }
+
+    #[test]
+    /// 按随机分二的取值范围穷举，概率应为 1/16、5/16、7/16、3/16
+    fn test_probability() {
+        // 下标为 6 到 9
+        let mut probability = [0.0; 10];
+
+        let range = |total: u8| 1..=(total - 2) / 4 * 4;
+
+        for first in range(DA_YAN_ZHI_SHU) {
+            let first_bian = DaYanBian::new(DA_YAN_ZHI_SHU, first);
+            let first_count = range(DA_YAN_ZHI_SHU).count() as f64;
+
+            for second in range(first_bian.rest()) {
+                let second_bian = DaYanBian::new(first_bian.rest(), second);
+                let second_count = range(first_bian.rest()).count() as f64;
+
+                for third in range(second_bian.rest()) {
+                    let third_count = range(second_bian.rest()).count() as f64;
+                    let number = DaYanYao::new([first, second, third]).number();
+
+                    probability[number as usize] +=
+                        1.0 / (first_count * second_count * third_count);
+                }
+            }
+        }
+
+        for (number, expected) in [(6, 1.0), (7, 5.0), (8, 7.0), (9, 3.0)] {
+            assert!((probability[number] - expected / 16.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    /// 随机推演的结果都是合法的
+    fn test_random() {
+        for _ in 0..100 {
+            let da_yan = DaYan::random();
+
+            for yao in da_yan.yao_list {
+                assert!((6..=9).contains(&yao.number()));
+                assert_eq!(yao.bian_list[0].total, 49);
+                assert_eq!(yao.bian_list[1].total, yao.bian_list[0].rest());
+                assert_eq!(yao.bian_list[2].total, yao.bian_list[1].rest());
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+//! 核心模块的错误类型
+
+use std::{error::Error, fmt::Display};
+
+use chrono::NaiveDate;
+
+use crate::core::models::ParseGuaError;
+
+/// 核心模块的错误
+#[derive(Debug)]
+pub enum CoreError {
+    /// 八卦的先天数超出范围，应为 1 到 8
+    Gua8Number(u8),
+    /// 无法识别输入的卦
+    ParseGua(ParseGuaError),
+    /// 六十四卦的资料解析失败
+    Gua64Info(serde_json::Error),
+    /// 日期超出农历可计算的范围
+    DateOutOfRange(NaiveDate),
+}
+
+impl Display for CoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CoreError::Gua8Number(num) => write!(f, "八卦序号应为 1 到 8，实际为 {num}"),
+            CoreError::ParseGua(err) => write!(f, "{err}"),
+            CoreError::Gua64Info(err) => write!(f, "六十四卦资料解析失败：{err}"),
+            CoreError::DateOutOfRange(date) => write!(
+                f,
+                "日期 {} 超出可计算的范围，请选择 1901 年到 2100 年之间的日期",
+                date.format("%Y-%m-%d")
+            ),
+        }
+    }
+}
+
+impl Error for CoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CoreError::ParseGua(err) => Some(err),
+            CoreError::Gua64Info(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseGuaError> for CoreError {
+    fn from(value: ParseGuaError) -> Self {
+        CoreError::ParseGua(value)
+    }
+}
+
+impl From<serde_json::Error> for CoreError {
+    fn from(value: serde_json::Error) -> Self {
+        CoreError::Gua64Info(value)
+    }
+}
@@ -0,0 +1,310 @@
+//! 天干、地支以及五行
+
+use std::fmt::Display;
+
+use chinese_lunisolar_calendar::EarthlyBranch;
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// 五行
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, Serialize, Deserialize)]
+pub enum WuXing {
+    木,
+    火,
+    土,
+    金,
+    水,
+}
+
+impl WuXing {
+    /// 所生的五行：木生火，火生土，土生金，金生水，水生木
+    pub const fn sheng(&self) -> WuXing {
+        match self {
+            WuXing::木 => WuXing::火,
+            WuXing::火 => WuXing::土,
+            WuXing::土 => WuXing::金,
+            WuXing::金 => WuXing::水,
+            WuXing::水 => WuXing::木,
+        }
+    }
+
+    /// 所克的五行：木克土，土克水，水克火，火克金，金克木
+    pub const fn ke(&self) -> WuXing {
+        match self {
+            WuXing::木 => WuXing::土,
+            WuXing::土 => WuXing::水,
+            WuXing::水 => WuXing::火,
+            WuXing::火 => WuXing::金,
+            WuXing::金 => WuXing::木,
+        }
+    }
+}
+
+/// 天干
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, Serialize, Deserialize)]
+pub enum TianGan {
+    甲,
+    乙,
+    丙,
+    丁,
+    戊,
+    己,
+    庚,
+    辛,
+    壬,
+    癸,
+}
+
+impl TianGan {
+    /// 所有天干，从甲到癸
+    pub const ALL: [TianGan; 10] = [
+        TianGan::甲,
+        TianGan::乙,
+        TianGan::丙,
+        TianGan::丁,
+        TianGan::戊,
+        TianGan::己,
+        TianGan::庚,
+        TianGan::辛,
+        TianGan::壬,
+        TianGan::癸,
+    ];
+
+    /// 序号，甲为 0
+    pub const fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// 根据序号获取天干，超出范围时循环
+    pub const fn from_index(index: usize) -> Self {
+        Self::ALL[index % 10]
+    }
+}
+
+/// 地支
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, Serialize, Deserialize)]
+pub enum DiZhi {
+    子,
+    丑,
+    寅,
+    卯,
+    辰,
+    巳,
+    午,
+    未,
+    申,
+    酉,
+    戌,
+    亥,
+}
+
+impl DiZhi {
+    /// 所有地支，从子到亥
+    pub const ALL: [DiZhi; 12] = [
+        DiZhi::子,
+        DiZhi::丑,
+        DiZhi::寅,
+        DiZhi::卯,
+        DiZhi::辰,
+        DiZhi::巳,
+        DiZhi::午,
+        DiZhi::未,
+        DiZhi::申,
+        DiZhi::酉,
+        DiZhi::戌,
+        DiZhi::亥,
+    ];
+
+    /// 序号，子为 0
+    pub const fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// 序数，子为 1，起卦时用于计数
+    pub const fn number(&self) -> u8 {
+        self.index() as u8 + 1
+    }
+
+    /// 根据序号获取地支，超出范围时循环
+    pub const fn from_index(index: usize) -> Self {
+        Self::ALL[index % 12]
+    }
+
+    /// 小时所在的时辰，子时为 23:00 到 01:00，之后每两个小时一个时辰
+    pub const fn from_hour(hour: u32) -> Self {
+        Self::from_index((hour as usize).div_ceil(2))
+    }
+
+    /// 相冲的地支：子午、丑未、寅申、卯酉、辰戌、巳亥
+    pub const fn chong(&self) -> DiZhi {
+        Self::from_index(self.index() + 6)
+    }
+
+    /// 六合的地支：子丑、寅亥、卯戌、辰酉、巳申、午未
+    pub const fn he(&self) -> DiZhi {
+        Self::from_index(13 - self.index())
+    }
+
+    /// 五行
+    pub const fn wu_xing(&self) -> WuXing {
+        match self {
+            DiZhi::寅 | DiZhi::卯 => WuXing::木,
+            DiZhi::巳 | DiZhi::午 => WuXing::火,
+            DiZhi::申 | DiZhi::酉 => WuXing::金,
+            DiZhi::亥 | DiZhi::子 => WuXing::水,
+            DiZhi::辰 | DiZhi::戌 | DiZhi::丑 | DiZhi::未 => WuXing::土,
+        }
+    }
+}
+
+impl From<EarthlyBranch> for DiZhi {
+    /// `EarthlyBranch` 的序数从 1 开始，子为 1
+    fn from(value: EarthlyBranch) -> Self {
+        Self::from_index(value.ordinal() as usize - 1)
+    }
+}
+
+/// 干支，如 `甲子`
+///
+/// 天干和地支的阴阳需要一致，才是六十甲子中的一个
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GanZhi {
+    /// 天干
+    pub gan: TianGan,
+    /// 地支
+    pub zhi: DiZhi,
+}
+
+impl GanZhi {
+    pub const fn new(gan: TianGan, zhi: DiZhi) -> Self {
+        Self { gan, zhi }
+    }
+
+    /// 根据六十甲子的序号获取干支，甲子为 0，超出范围时循环
+    pub const fn from_index(index: usize) -> Self {
+        Self::new(TianGan::from_index(index), DiZhi::from_index(index))
+    }
+
+    /// 六十甲子的序号，甲子为 0
+    pub const fn index(&self) -> usize {
+        // 满足 index % 10 == 干，index % 12 == 支
+        (self.gan.index() * 6 + self.zhi.index() * 55) % 60
+    }
+
+    /// 日干支
+    ///
+    /// 以儒略日推算，每天从 0 点开始，不按子时换日
+    pub fn from_day(date: NaiveDate) -> Self {
+        // 公元元年 1 月 1 日的前一天为儒略日 1721425，儒略日加 49 后除以 60 的余数即为日干支序号
+        let julian_day = date.num_days_from_ce() as i64 + 1_721_425;
+
+        Self::from_index((julian_day + 49).rem_euclid(60) as usize)
+    }
+
+    /// 旬空，即所在旬中没有配上天干的两个地支
+    pub const fn xun_kong(&self) -> [DiZhi; 2] {
+        // 旬首甲所配地支的序号
+        let xun_shou = self.zhi.index() + 12 - self.gan.index();
+
+        [
+            DiZhi::from_index(xun_shou + 10),
+            DiZhi::from_index(xun_shou + 11),
+        ]
+    }
+}
+
+impl Display for GanZhi {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.gan, self.zhi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chinese_lunisolar_calendar::EarthlyBranch;
+    use chrono::NaiveDate;
+
+    use super::{DiZhi, GanZhi, TianGan, WuXing};
+
+    #[test]
+    /// 测试六十甲子、日干支以及旬空
+    fn test_gan_zhi() {
+        for index in 0..60 {
+            assert_eq!(GanZhi::from_index(index).index(), index);
+        }
+
+        assert_eq!(GanZhi::new(TianGan::甲, DiZhi::戌).index(), 10);
+        assert_eq!(GanZhi::new(TianGan::癸, DiZhi::亥).index(), 59);
+
+        let day = |y, m, d| GanZhi::from_day(NaiveDate::from_ymd_opt(y, m, d).unwrap());
+        assert_eq!(day(2000, 1, 1).to_string(), "戊午");
+        assert_eq!(day(2024, 2, 10).to_string(), "甲辰");
+        assert_eq!(day(1949, 10, 1).to_string(), "甲子");
+
+        // 戊午在甲寅旬，子丑空
+        assert_eq!(day(2000, 1, 1).xun_kong(), [DiZhi::子, DiZhi::丑]);
+        // 甲子旬，戌亥空
+        assert_eq!(GanZhi::from_index(0).xun_kong(), [DiZhi::戌, DiZhi::亥]);
+        assert_eq!(GanZhi::from_index(9).xun_kong(), [DiZhi::戌, DiZhi::亥]);
+    }
+
+    #[test]
+    /// 测试从 EarthlyBranch 转换
+    fn test_di_zhi_from_earthly_branch() {
+        assert_eq!(DiZhi::from(EarthlyBranch::First), DiZhi::子);
+        assert_eq!(DiZhi::from(EarthlyBranch::Fifth), DiZhi::辰);
+        assert_eq!(DiZhi::from(EarthlyBranch::Twelfth), DiZhi::亥);
+    }
+
+    #[test]
+    /// 测试小时对应的时辰，23 点已是下一个子时
+    fn test_di_zhi_from_hour() {
+        let shi_chen: Vec<_> = (0..24).map(DiZhi::from_hour).collect();
+
+        assert_eq!(shi_chen[0], DiZhi::子);
+        assert_eq!(shi_chen[1], DiZhi::丑);
+        assert_eq!(shi_chen[8], DiZhi::辰);
+        assert_eq!(shi_chen[12], DiZhi::午);
+        assert_eq!(shi_chen[22], DiZhi::亥);
+        assert_eq!(shi_chen[23], DiZhi::子);
+
+        // 除子时跨越午夜外，每个时辰都是连续的两个小时
+        for (index, zhi) in DiZhi::ALL.into_iter().enumerate().skip(1) {
+            assert_eq!(shi_chen[index * 2 - 1], zhi);
+            assert_eq!(shi_chen[index * 2], zhi);
+        }
+    }
+
+    #[test]
+    /// 测试地支六冲、六合
+    fn test_chong_he() {
+        assert_eq!(DiZhi::子.chong(), DiZhi::午);
+        assert_eq!(DiZhi::亥.chong(), DiZhi::巳);
+        assert_eq!(DiZhi::子.he(), DiZhi::丑);
+        assert_eq!(DiZhi::寅.he(), DiZhi::亥);
+        assert_eq!(DiZhi::午.he(), DiZhi::未);
+
+        for zhi in DiZhi::ALL {
+            assert_eq!(zhi.chong().chong(), zhi);
+            assert_eq!(zhi.he().he(), zhi);
+        }
+    }
+
+    #[test]
+    /// 测试五行生克，生和克各自五步回到自身
+    fn test_wu_xing() {
+        assert_eq!(WuXing::水.sheng(), WuXing::木);
+        assert_eq!(WuXing::水.ke(), WuXing::火);
+
+        for wu_xing in [WuXing::木, WuXing::火, WuXing::土, WuXing::金, WuXing::水] {
+            let sheng = (0..5).fold(wu_xing, |current, _| current.sheng());
+            let ke = (0..5).fold(wu_xing, |current, _| current.ke());
+
+            assert_eq!(sheng, wu_xing);
+            assert_eq!(ke, wu_xing);
+            // 我克者，即我所生者所生
+            assert_eq!(wu_xing.ke(), wu_xing.sheng().sheng());
+        }
+    }
+}
@@ -0,0 +1,538 @@
+//! 二十四节气
+//!
+//! 节气时刻由太阳视黄经推算，不依赖网络和查表，已有实测力学时差的年份误差在一分钟以内，
+//! 往后取决于力学时差的外推，到 2100 年可能相差数分钟
+
+use std::f64::consts::PI;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+/// 支持计算的年份范围
+pub const YEAR_RANGE: std::ops::RangeInclusive<i32> = 1901..=2100;
+
+/// 二十四节气，按公历一年中的顺序，从小寒开始
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, Serialize, Deserialize)]
+pub enum JieQi {
+    小寒,
+    大寒,
+    立春,
+    雨水,
+    惊蛰,
+    春分,
+    清明,
+    谷雨,
+    立夏,
+    小满,
+    芒种,
+    夏至,
+    小暑,
+    大暑,
+    立秋,
+    处暑,
+    白露,
+    秋分,
+    寒露,
+    霜降,
+    立冬,
+    小雪,
+    大雪,
+    冬至,
+}
+
+impl JieQi {
+    /// 所有节气，从小寒到冬至
+    pub const ALL: [JieQi; 24] = [
+        JieQi::小寒,
+        JieQi::大寒,
+        JieQi::立春,
+        JieQi::雨水,
+        JieQi::惊蛰,
+        JieQi::春分,
+        JieQi::清明,
+        JieQi::谷雨,
+        JieQi::立夏,
+        JieQi::小满,
+        JieQi::芒种,
+        JieQi::夏至,
+        JieQi::小暑,
+        JieQi::大暑,
+        JieQi::立秋,
+        JieQi::处暑,
+        JieQi::白露,
+        JieQi::秋分,
+        JieQi::寒露,
+        JieQi::霜降,
+        JieQi::立冬,
+        JieQi::小雪,
+        JieQi::大雪,
+        JieQi::冬至,
+    ];
+
+    /// 序号，小寒为 0
+    pub const fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// 节气开始时太阳的视黄经，单位为度，小寒为 285°
+    pub const fn longitude(&self) -> f64 {
+        ((285 + self.index() * 15) % 360) as f64
+    }
+
+    /// 某个时刻所处的节气
+    pub fn from_time<Tz: TimeZone>(datetime: &DateTime<Tz>) -> Self {
+        let longitude = sun_longitude(datetime.naive_utc());
+        let index = ((longitude - 285.).rem_euclid(360.) / 15.) as usize;
+
+        Self::ALL[index % 24]
+    }
+
+    /// 某个时刻所处的节气，以及该节气开始的时刻
+    pub fn current<Tz: TimeZone>(datetime: &DateTime<Tz>) -> Option<(Self, DateTime<Utc>)> {
+        let jie_qi = Self::from_time(datetime);
+        let utc = datetime.naive_utc();
+
+        // 一月初还在上一年的冬至
+        let year = match jie_qi == JieQi::冬至 && utc.month() == 1 {
+            true => utc.year() - 1,
+            false => utc.year(),
+        };
+
+        jie_qi.time(year).map(|time| (jie_qi, time))
+    }
+
+    /// 节气在某一年开始的时刻，超出支持的年份时返回 `None`
+    pub fn time(&self, year: i32) -> Option<DateTime<Utc>> {
+        if !YEAR_RANGE.contains(&year) {
+            return None;
+        }
+
+        // 节气大约每 15.22 天一个，以此为中心前后各找五天
+        let guess = Utc
+            .with_ymd_and_hms(year, 1, 6, 0, 0, 0)
+            .single()?
+            .naive_utc()
+            + Duration::seconds((self.index() as f64 * 15.2184 * 86400.) as i64);
+
+        let mut start = guess - Duration::days(5);
+        let mut end = guess + Duration::days(5);
+
+        // 二分查找太阳到达该黄经的时刻，精确到秒
+        while end - start > Duration::seconds(1) {
+            let middle = start + (end - start) / 2;
+
+            // 与目标黄经的差，范围为 -180° 到 180°
+            let diff = (sun_longitude(middle) - self.longitude() + 540.).rem_euclid(360.) - 180.;
+
+            match diff < 0. {
+                true => start = middle,
+                false => end = middle,
+            }
+        }
+
+        Some(end.and_utc())
+    }
+}
+
+/// 一年中所有节气开始的时刻，超出支持的年份时返回 `None`
+pub fn jie_qi_list(year: i32) -> Option<Vec<(JieQi, DateTime<Utc>)>> {
+    JieQi::ALL
+        .into_iter()
+        .map(|jie_qi| jie_qi.time(year).map(|time| (jie_qi, time)))
+        .collect()
+}
+
+/// 太阳视黄经，单位为度
+///
+/// 使用 VSOP87 的截断序列计算地球日心黄经，再加上章动和光行差
+///
+/// * `utc` - 世界时
+fn sun_longitude(utc: NaiveDateTime) -> f64 {
+    // 儒略千年数，从 J2000.0 起算，力学时
+    let year = utc.year() as f64 + utc.ordinal0() as f64 / 365.25;
+    let tau = (julian_day(utc) + delta_t(year) / 86400. - 2_451_545.) / 365_250.;
+
+    let series = |terms: &[(f64, f64, f64)]| -> f64 {
+        terms.iter().map(|(a, b, c)| a * (b + c * tau).cos()).sum()
+    };
+
+    let earth = [L0, L1, L2, L3, L4]
+        .iter()
+        .rev()
+        .fold(0., |sum, terms| sum * tau + series(terms))
+        / 1e8;
+
+    // 太阳的地心黄经即地球日心黄经加 180°
+    let longitude = earth.to_degrees() + 180.;
+
+    // 转到 FK5 坐标系
+    let fk5 = -0.09033 / 3600.;
+
+    // 章动，只取主要项
+    let t = tau * 10.;
+    let omega = (125.04452 - 1934.136261 * t).to_radians();
+    let sun = (280.4665 + 36000.7698 * t).to_radians();
+    let moon = (218.3165 + 481267.8813 * t).to_radians();
+    let nutation = (-17.20 * omega.sin() - 1.32 * (2. * sun).sin() - 0.23 * (2. * moon).sin()
+        + 0.21 * (2. * omega).sin())
+        / 3600.;
+
+    // 光行差
+    let aberration = -20.4898 / 3600.;
+
+    (longitude + fk5 + nutation + aberration).rem_euclid(360.)
+}
+
+/// 儒略日
+fn julian_day(utc: NaiveDateTime) -> f64 {
+    let j2000 = Utc
+        .with_ymd_and_hms(2000, 1, 1, 12, 0, 0)
+        .unwrap()
+        .naive_utc();
+
+    2_451_545. + (utc - j2000).num_seconds() as f64 / 86400.
+}
+
+/// 力学时与世界时之差，单位为秒
+///
+/// 使用 Espenak 和 Meeus 的分段多项式，2005 年以前与实测值相差一两秒，
+/// 之后是外推值，到 2024 年约偏大五秒，越往后越不确定，2100 年可能相差一分钟以上
+///
+/// * `year` - 带小数的年份
+fn delta_t(year: f64) -> f64 {
+    let u = (year - 1820.) / 100.;
+
+    match year {
+        y if y < 1900. => -20. + 32. * u * u,
+        y if y < 1920. => {
+            let t = y - 1900.;
+            -2.79 + 1.494119 * t - 0.0598939 * t.powi(2) + 0.0061966 * t.powi(3)
+                - 0.000197 * t.powi(4)
+        }
+        y if y < 1941. => {
+            let t = y - 1920.;
+            21.20 + 0.84493 * t - 0.0761 * t.powi(2) + 0.0020936 * t.powi(3)
+        }
+        y if y < 1961. => {
+            let t = y - 1950.;
+            29.07 + 0.407 * t - t.powi(2) / 233. + t.powi(3) / 2547.
+        }
+        y if y < 1986. => {
+            let t = y - 1975.;
+            45.45 + 1.067 * t - t.powi(2) / 260. - t.powi(3) / 718.
+        }
+        y if y < 2005. => {
+            let t = y - 2000.;
+            63.86 + 0.3345 * t - 0.060374 * t.powi(2)
+                + 0.0017275 * t.powi(3)
+                + 0.000651814 * t.powi(4)
+                + 0.00002373599 * t.powi(5)
+        }
+        y if y < 2050. => {
+            let t = y - 2000.;
+            62.92 + 0.32217 * t + 0.005589 * t.powi(2)
+        }
+        y if y < 2150. => -20. + 32. * u * u - 0.5628 * (2150. - y),
+        _ => -20. + 32. * u * u,
+    }
+}
+
+// VSOP87 地球日心黄经的主要项：振幅、相位、频率
+// 取自 Meeus《天文算法》附录，精度约为 1 角秒
+
+const L0: &[(f64, f64, f64)] = &[
+    (175347046., 0., 0.),
+    (3341656., 4.6692568, 6283.07585),
+    (34894., 4.6261, 12566.1517),
+    (3497., 2.7441, 5753.3849),
+    (3418., 2.8289, 3.5231),
+    (3136., 3.6277, 77713.7715),
+    (2676., 4.4181, 7860.4194),
+    (2343., 6.1352, 3930.2097),
+    (1324., 0.7425, 11506.7698),
+    (1273., 2.0371, 529.691),
+    (1199., 1.1096, 1577.3435),
+    (990., 5.233, 5884.927),
+    (902., 2.045, 26.298),
+    (857., 3.508, 398.149),
+    (780., 1.179, 5223.694),
+    (753., 2.533, 5507.553),
+    (505., 4.583, 18849.228),
+    (492., 4.205, 775.523),
+    (357., 2.92, 0.067),
+    (317., 5.849, 11790.629),
+    (284., 1.899, 796.298),
+    (271., 0.315, 10977.079),
+    (243., 0.345, 5486.778),
+    (206., 4.806, 2544.314),
+    (205., 1.869, 5573.143),
+    (202., 2.458, 6069.777),
+    (156., 0.833, 213.299),
+    (132., 3.411, 2942.463),
+    (126., 1.083, 20.775),
+    (115., 0.645, 0.98),
+    (103., 0.636, 4694.003),
+    (102., 0.976, 15720.839),
+    (102., 4.267, 7.114),
+    (99., 6.21, 2146.17),
+    (98., 0.68, 155.42),
+    (86., 5.98, 161000.69),
+    (85., 1.3, 6275.96),
+    (85., 3.67, 71430.7),
+    (80., 1.81, 17260.15),
+    (79., 3.04, 12036.46),
+    (75., 1.76, 5088.63),
+    (74., 3.5, 3154.69),
+    (74., 4.68, 801.82),
+    (70., 0.83, 9437.76),
+    (62., 3.98, 8827.39),
+    (61., 1.82, 7084.9),
+    (57., 2.78, 6286.6),
+    (56., 4.39, 14143.5),
+    (56., 3.47, 6279.55),
+    (52., 0.19, 12139.55),
+    (52., 1.33, 1748.02),
+    (51., 0.28, 5856.48),
+    (49., 0.49, 1194.45),
+    (41., 5.37, 8429.24),
+    (41., 2.4, 19651.05),
+    (39., 6.17, 10447.39),
+    (37., 6.04, 10213.29),
+    (37., 2.57, 1059.38),
+    (36., 1.71, 2352.87),
+    (36., 1.78, 6812.77),
+    (33., 0.59, 17789.85),
+    (30., 0.44, 83996.85),
+    (30., 2.74, 1349.87),
+    (25., 3.16, 4690.48),
+];
+
+const L1: &[(f64, f64, f64)] = &[
+    (628331966747., 0., 0.),
+    (206059., 2.678235, 6283.07585),
+    (4303., 2.6351, 12566.1517),
+    (425., 1.59, 3.523),
+    (119., 5.796, 26.298),
+    (109., 2.966, 1577.344),
+    (93., 2.59, 18849.23),
+    (72., 1.14, 529.69),
+    (68., 1.87, 398.15),
+    (67., 4.41, 5507.55),
+    (59., 2.89, 5223.69),
+    (56., 2.17, 155.42),
+    (45., 0.4, 796.3),
+    (36., 0.47, 775.52),
+    (29., 2.65, 7.11),
+    (21., 5.34, 0.98),
+    (19., 1.85, 5486.78),
+    (19., 4.97, 213.3),
+    (17., 2.99, 6275.96),
+    (16., 0.03, 2544.31),
+    (16., 1.43, 2146.17),
+    (15., 1.21, 10977.08),
+    (12., 2.83, 1748.02),
+    (12., 3.26, 5088.63),
+    (12., 5.27, 1194.45),
+    (12., 2.08, 4694.),
+    (11., 0.77, 553.57),
+    (10., 1.3, 6286.6),
+    (10., 4.24, 1349.87),
+    (9., 2.7, 242.73),
+    (9., 5.64, 951.72),
+    (8., 5.3, 2352.87),
+    (6., 2.65, 9437.76),
+    (6., 4.67, 4690.48),
+];
+
+const L2: &[(f64, f64, f64)] = &[
+    (52919., 0., 0.),
+    (8720., 1.0721, 6283.0758),
+    (309., 0.867, 12566.152),
+    (27., 0.05, 3.52),
+    (16., 5.19, 26.3),
+    (16., 3.68, 155.42),
+    (10., 0.76, 18849.23),
+    (9., 2.06, 77713.77),
+    (7., 0.83, 775.52),
+    (5., 4.66, 1577.34),
+    (4., 1.03, 7.11),
+    (4., 3.44, 5573.14),
+    (3., 5.14, 796.3),
+    (3., 6.05, 5507.55),
+    (3., 1.19, 242.73),
+    (3., 6.12, 529.69),
+    (3., 0.31, 398.15),
+    (3., 2.28, 553.57),
+    (2., 4.38, 5223.69),
+    (2., 3.75, 0.98),
+];
+
+const L3: &[(f64, f64, f64)] = &[
+    (289., 5.844, 6283.076),
+    (35., 0., 0.),
+    (17., 5.49, 12566.15),
+    (3., 5.2, 155.42),
+    (1., 4.72, 3.52),
+    (1., 5.3, 18849.23),
+    (1., 5.97, 242.73),
+];
+
+const L4: &[(f64, f64, f64)] = &[(114., PI, 0.), (8., 4.13, 6283.08), (1., 3.84, 12566.15)];
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Datelike, FixedOffset, TimeZone};
+
+    use super::{JieQi, YEAR_RANGE, jie_qi_list};
+
+    /// 1901 年到 2050 年间的四立和二分二至，北京时间，精确到分钟
+    ///
+    /// 不是公开发布的表，而是用 `calendrical_calculations` 库
+    /// （Reingold 和 Dershowitz《Calendrical Calculations》算法的实现）另外算出的，
+    /// 其中 2000 年的二分二至与美国海军天文台公布的时刻在一分钟以内。
+    /// 2100 年两边的力学时差都是外推值，差别比这里的误差还大，所以不列入
+    const REFERENCE: [(i32, JieQi, u32, u32, u32, u32); 32] = [
+        (1901, JieQi::立春, 2, 4, 19, 40),
+        (1901, JieQi::春分, 3, 21, 15, 23),
+        (1901, JieQi::立夏, 5, 6, 13, 51),
+        (1901, JieQi::夏至, 6, 22, 11, 28),
+        (1901, JieQi::立秋, 8, 8, 14, 46),
+        (1901, JieQi::秋分, 9, 24, 2, 9),
+        (1901, JieQi::立冬, 11, 8, 10, 34),
+        (1901, JieQi::冬至, 12, 22, 20, 36),
+        (1950, JieQi::立春, 2, 4, 17, 21),
+        (1950, JieQi::春分, 3, 21, 12, 35),
+        (1950, JieQi::立夏, 5, 6, 10, 25),
+        (1950, JieQi::夏至, 6, 22, 7, 36),
+        (1950, JieQi::立秋, 8, 8, 10, 55),
+        (1950, JieQi::秋分, 9, 23, 22, 44),
+        (1950, JieQi::立冬, 11, 8, 7, 43),
+        (1950, JieQi::冬至, 12, 22, 18, 13),
+        (2000, JieQi::立春, 2, 4, 20, 40),
+        (2000, JieQi::春分, 3, 20, 15, 35),
+        (2000, JieQi::立夏, 5, 5, 12, 50),
+        (2000, JieQi::夏至, 6, 21, 9, 48),
+        (2000, JieQi::立秋, 8, 7, 13, 3),
+        (2000, JieQi::秋分, 9, 23, 1, 28),
+        (2000, JieQi::立冬, 11, 7, 10, 48),
+        (2000, JieQi::冬至, 12, 21, 21, 37),
+        (2050, JieQi::立春, 2, 3, 23, 44),
+        (2050, JieQi::春分, 3, 20, 18, 19),
+        (2050, JieQi::立夏, 5, 5, 15, 2),
+        (2050, JieQi::夏至, 6, 21, 11, 33),
+        (2050, JieQi::立秋, 8, 7, 14, 52),
+        (2050, JieQi::秋分, 9, 23, 3, 28),
+        (2050, JieQi::立冬, 11, 7, 13, 33),
+        (2050, JieQi::冬至, 12, 22, 0, 38),
+    ];
+
+    /// 2024 年节气时刻对照表，北京时间
+    const REFERENCE_2024: [(JieQi, u32, u32, u32, u32); 24] = [
+        (JieQi::小寒, 1, 6, 4, 49),
+        (JieQi::大寒, 1, 20, 22, 7),
+        (JieQi::立春, 2, 4, 16, 27),
+        (JieQi::雨水, 2, 19, 12, 13),
+        (JieQi::惊蛰, 3, 5, 10, 23),
+        (JieQi::春分, 3, 20, 11, 6),
+        (JieQi::清明, 4, 4, 15, 2),
+        (JieQi::谷雨, 4, 19, 21, 59),
+        (JieQi::立夏, 5, 5, 8, 10),
+        (JieQi::小满, 5, 20, 20, 59),
+        (JieQi::芒种, 6, 5, 12, 10),
+        (JieQi::夏至, 6, 21, 4, 51),
+        (JieQi::小暑, 7, 6, 22, 20),
+        (JieQi::大暑, 7, 22, 15, 44),
+        (JieQi::立秋, 8, 7, 8, 9),
+        (JieQi::处暑, 8, 22, 22, 55),
+        (JieQi::白露, 9, 7, 11, 11),
+        (JieQi::秋分, 9, 22, 20, 44),
+        (JieQi::寒露, 10, 8, 3, 0),
+        (JieQi::霜降, 10, 23, 6, 15),
+        (JieQi::立冬, 11, 7, 6, 20),
+        (JieQi::小雪, 11, 22, 3, 56),
+        (JieQi::大雪, 12, 6, 23, 17),
+        (JieQi::冬至, 12, 21, 17, 21),
+    ];
+
+    #[test]
+    /// 与对照表相差不超过一分钟
+    fn test_reference_2024() {
+        let beijing = FixedOffset::east_opt(8 * 3600).unwrap();
+        let list = jie_qi_list(2024).unwrap();
+
+        for ((jie_qi, time), (expected, month, day, hour, minute)) in
+            list.into_iter().zip(REFERENCE_2024)
+        {
+            let expected_time = beijing
+                .with_ymd_and_hms(2024, month, day, hour, minute, 0)
+                .unwrap();
+
+            assert_eq!(jie_qi, expected);
+            assert!(
+                (time - expected_time.to_utc()).num_seconds().abs() <= 60,
+                "{jie_qi}: {}",
+                time.with_timezone(&beijing)
+            );
+        }
+    }
+
+    #[test]
+    /// 与各年份的对照表相差不超过一分钟
+    fn test_reference() {
+        let beijing = FixedOffset::east_opt(8 * 3600).unwrap();
+
+        for (year, jie_qi, month, day, hour, minute) in REFERENCE {
+            let time = jie_qi.time(year).unwrap();
+            let expected_time = beijing
+                .with_ymd_and_hms(year, month, day, hour, minute, 0)
+                .unwrap();
+
+            assert!(
+                (time - expected_time.to_utc()).num_seconds().abs() <= 60,
+                "{year} {jie_qi}: {}",
+                time.with_timezone(&beijing)
+            );
+        }
+    }
+
+    #[test]
+    /// 支持范围内每年的节气都按顺序排列，间隔在 14 到 17 天之间
+    fn test_all_years() {
+        assert_eq!(jie_qi_list(1900), None);
+        assert_eq!(jie_qi_list(2101), None);
+
+        for year in YEAR_RANGE {
+            let list = jie_qi_list(year).unwrap();
+
+            for pair in list.windows(2) {
+                let days = (pair[1].1 - pair[0].1).num_hours() as f64 / 24.;
+                assert!((14. ..17.).contains(&days), "{year} {}", pair[1].0);
+            }
+
+            // 立春在 2 月 3 日到 5 日之间，冬至在 12 月 21 日到 23 日之间
+            let li_chun = list[JieQi::立春.index()].1;
+            assert!((3..=5).contains(&li_chun.day()), "{year}");
+            let dong_zhi = list[JieQi::冬至.index()].1;
+            assert!((20..=23).contains(&dong_zhi.day()), "{year}");
+        }
+    }
+
+    #[test]
+    /// 测试某一时刻所处的节气
+    fn test_current() {
+        let beijing = FixedOffset::east_opt(8 * 3600).unwrap();
+
+        let time = beijing.with_ymd_and_hms(2024, 2, 4, 16, 20, 0).unwrap();
+        assert_eq!(JieQi::from_time(&time), JieQi::大寒);
+
+        let time = beijing.with_ymd_and_hms(2024, 2, 4, 16, 35, 0).unwrap();
+        assert_eq!(JieQi::from_time(&time), JieQi::立春);
+
+        // 一月初仍是上一年的冬至
+        let time = beijing.with_ymd_and_hms(2024, 1, 2, 12, 0, 0).unwrap();
+        let (jie_qi, start) = JieQi::current(&time).unwrap();
+        assert_eq!(jie_qi, JieQi::冬至);
+        assert_eq!(start.year(), 2023);
+    }
+}
@@ -0,0 +1,203 @@
+//! 梅花易数的体用断法
+//!
+//! 动爻所在的卦为用，另一个卦为体，以体卦的五行为我，看本卦、互卦、变卦中各卦对体的生克
+
+use strum::Display;
+
+use crate::core::{
+    ba_gua::GuaResult,
+    gan_zhi::WuXing,
+    models::{Gua8, Gua64YaoIndex},
+};
+
+/// 体用之间的生克关系
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum TiYong {
+    比和,
+    用生体,
+    体生用,
+    用克体,
+    体克用,
+}
+
+impl TiYong {
+    /// * `ti` - 体卦的五行
+    /// * `yong` - 用卦的五行
+    pub fn new(ti: WuXing, yong: WuXing) -> Self {
+        if ti == yong {
+            TiYong::比和
+        } else if yong.sheng() == ti {
+            TiYong::用生体
+        } else if ti.sheng() == yong {
+            TiYong::体生用
+        } else if yong.ke() == ti {
+            TiYong::用克体
+        } else {
+            TiYong::体克用
+        }
+    }
+
+    /// 吉凶
+    pub const fn ji_xiong(&self) -> &'static str {
+        match self {
+            TiYong::用生体 => "大吉",
+            TiYong::比和 => "吉",
+            TiYong::体克用 => "小吉",
+            TiYong::体生用 => "小凶",
+            TiYong::用克体 => "凶",
+        }
+    }
+}
+
+/// 与体卦比较的一个卦
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TiYongItem {
+    /// 来源，如 `本卦用卦`、`互卦上卦`
+    pub label: &'static str,
+    /// 八卦
+    pub gua: Gua8,
+    /// 与体卦的关系
+    pub ti_yong: TiYong,
+}
+
+/// 体用分析结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MeiHuaReading {
+    /// 体卦
+    pub ti: Gua8,
+    /// 体卦是否为上卦
+    pub ti_is_shang: bool,
+    /// 依次为本卦用卦、互卦上下卦、变卦用卦
+    pub items: Vec<TiYongItem>,
+}
+
+impl MeiHuaReading {
+    /// 如 `体卦：乾（金，下卦）`
+    pub fn ti_display(&self) -> String {
+        let position = if self.ti_is_shang { "上卦" } else { "下卦" };
+
+        format!(
+            "体卦：{}（{}，{}）",
+            self.ti.name(),
+            self.ti.wu_xing(),
+            position
+        )
+    }
+}
+
+/// 根据算卦结果分析体用
+///
+/// 梅花易数只有一个动爻，没有动爻或有多个动爻时无法分体用，返回 `None`
+pub fn ti_yong(result: &GuaResult) -> Option<MeiHuaReading> {
+    let [dong_yao] = result.dong_yao.as_slice() else {
+        return None;
+    };
+
+    let ben_gua = &result.ben_gua;
+    let bian_gua = result.bian_gua()?;
+
+    // 动爻在下卦，则下卦为用，上卦为体
+    let ti_is_shang = *dong_yao <= Gua64YaoIndex::Third;
+
+    let (ti, yong, bian_yong) = match ti_is_shang {
+        true => (ben_gua.shang(), ben_gua.xia(), bian_gua.xia()),
+        false => (ben_gua.xia(), ben_gua.shang(), bian_gua.shang()),
+    };
+
+    let item = |label, gua: Gua8| TiYongItem {
+        label,
+        gua,
+        ti_yong: TiYong::new(ti.wu_xing(), gua.wu_xing()),
+    };
+
+    Some(MeiHuaReading {
+        ti,
+        ti_is_shang,
+        items: vec![
+            item("本卦用卦", yong),
+            item("互卦上卦", result.hu_gua.shang()),
+            item("互卦下卦", result.hu_gua.xia()),
+            item("变卦用卦", bian_yong),
+        ],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{
+        ba_gua::{BaGuaCalculator, DongYaoFormula, GuaResult, QiGuaSource},
+        gan_zhi::WuXing,
+        models::{Gua8, Gua64, Gua64YaoIndex, ShuZi, ShuZiMode},
+    };
+
+    use super::{TiYong, ti_yong};
+
+    #[test]
+    /// 测试五行生克得出的体用关系
+    fn test_ti_yong_relation() {
+        assert_eq!(TiYong::new(WuXing::金, WuXing::金), TiYong::比和);
+        assert_eq!(TiYong::new(WuXing::金, WuXing::土), TiYong::用生体);
+        assert_eq!(TiYong::new(WuXing::金, WuXing::水), TiYong::体生用);
+        assert_eq!(TiYong::new(WuXing::金, WuXing::火), TiYong::用克体);
+        assert_eq!(TiYong::new(WuXing::金, WuXing::木), TiYong::体克用);
+    }
+
+    #[test]
+    /// 泰卦五爻动：坤为用，乾为体
+    fn test_ti_yong() {
+        let source = QiGuaSource::TwoNumber {
+            shang_num: ShuZi::from(128),
+            xia_num: ShuZi::from(33),
+            mode: ShuZiMode::取余,
+            formula: DongYaoFormula::Sum,
+        };
+        let result =
+            BaGuaCalculator::calculate_from_two_numbers(128, 33, 128 + 33, source).unwrap();
+
+        let reading = ti_yong(&result).unwrap();
+        assert_eq!(reading.ti, Gua8::乾);
+        assert!(!reading.ti_is_shang);
+        assert_eq!(reading.ti_display(), "体卦：乾（金，下卦）");
+
+        let items: Vec<(Gua8, TiYong)> = reading
+            .items
+            .iter()
+            .map(|item| (item.gua, item.ti_yong))
+            .collect();
+
+        assert_eq!(
+            items,
+            [
+                // 坤土生乾金
+                (Gua8::坤, TiYong::用生体),
+                // 互卦归妹，震木、兑金
+                (Gua8::震, TiYong::体克用),
+                (Gua8::兑, TiYong::比和),
+                // 变卦需，坎水
+                (Gua8::坎, TiYong::体生用),
+            ]
+        );
+        assert_eq!(reading.items[0].ti_yong.ji_xiong(), "大吉");
+    }
+
+    #[test]
+    /// 没有动爻或多个动爻时不分体用
+    fn test_ti_yong_without_single_dong_yao() {
+        let source = QiGuaSource::TwoNumber {
+            shang_num: ShuZi::from(0),
+            xia_num: ShuZi::from(0),
+            mode: ShuZiMode::取余,
+            formula: DongYaoFormula::Sum,
+        };
+
+        let result = GuaResult::new(Gua64::泰, vec![], source.clone());
+        assert_eq!(ti_yong(&result), None);
+
+        let result = GuaResult::new(
+            Gua64::泰,
+            vec![Gua64YaoIndex::First, Gua64YaoIndex::Sixth],
+            source,
+        );
+        assert_eq!(ti_yong(&result), None);
+    }
+}
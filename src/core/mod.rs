@@ -1,4 +1,15 @@
 pub mod ba_gua;
 pub mod basic;
+pub mod calendar;
+pub mod da_yan;
+pub mod error;
+pub mod gan_zhi;
+pub mod jie_qi;
+pub mod mei_hua;
 pub mod models;
+pub mod na_jia;
+pub mod pai_pan;
 pub mod utils;
+pub mod wang_shuai;
+pub mod yao_gua;
+pub mod zhu_xi;
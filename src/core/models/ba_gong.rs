@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Gua8, Gua64, Gua64YaoIndex};
+
+/// 京房八宫的顺序：乾、震、坎、艮、坤、巽、离、兑
+pub const BA_GONG_LIST: [Gua8; 8] = [
+    Gua8::乾,
+    Gua8::震,
+    Gua8::坎,
+    Gua8::艮,
+    Gua8::坤,
+    Gua8::巽,
+    Gua8::离,
+    Gua8::兑,
+];
+
+/// 卦在宫中的世代
+///
+/// 本宫卦从初爻开始逐爻变化，得到一世至五世卦，
+/// 五世卦的四爻复变为游魂卦，游魂卦的下卦复原为归魂卦
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GongShi {
+    本宫,
+    一世,
+    二世,
+    三世,
+    四世,
+    五世,
+    游魂,
+    归魂,
+}
+
+impl GongShi {
+    /// 所有世代，按宫中顺序排列
+    pub const ALL: [GongShi; 8] = [
+        GongShi::本宫,
+        GongShi::一世,
+        GongShi::二世,
+        GongShi::三世,
+        GongShi::四世,
+        GongShi::五世,
+        GongShi::游魂,
+        GongShi::归魂,
+    ];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            GongShi::本宫 => "本宫",
+            GongShi::一世 => "一世",
+            GongShi::二世 => "二世",
+            GongShi::三世 => "三世",
+            GongShi::四世 => "四世",
+            GongShi::五世 => "五世",
+            GongShi::游魂 => "游魂",
+            GongShi::归魂 => "归魂",
+        }
+    }
+
+    /// 相对本宫卦变化的爻
+    pub const fn dong_yao(&self) -> &'static [Gua64YaoIndex] {
+        use Gua64YaoIndex::*;
+
+        match self {
+            GongShi::本宫 => &[],
+            GongShi::一世 => &[First],
+            GongShi::二世 => &[First, Second],
+            GongShi::三世 => &[First, Second, Third],
+            GongShi::四世 => &[First, Second, Third, Fourth],
+            GongShi::五世 => &[First, Second, Third, Fourth, Fifth],
+            GongShi::游魂 => &[First, Second, Third, Fifth],
+            GongShi::归魂 => &[Fifth],
+        }
+    }
+
+    /// 世爻
+    pub const fn shi_yao(&self) -> Gua64YaoIndex {
+        match self {
+            GongShi::本宫 => Gua64YaoIndex::Sixth,
+            GongShi::一世 => Gua64YaoIndex::First,
+            GongShi::二世 => Gua64YaoIndex::Second,
+            GongShi::三世 => Gua64YaoIndex::Third,
+            GongShi::四世 | GongShi::游魂 => Gua64YaoIndex::Fourth,
+            GongShi::五世 => Gua64YaoIndex::Fifth,
+            GongShi::归魂 => Gua64YaoIndex::Third,
+        }
+    }
+
+    /// 应爻，与世爻相隔两爻
+    pub const fn ying_yao(&self) -> Gua64YaoIndex {
+        match self.shi_yao() {
+            Gua64YaoIndex::First => Gua64YaoIndex::Fourth,
+            Gua64YaoIndex::Second => Gua64YaoIndex::Fifth,
+            Gua64YaoIndex::Third => Gua64YaoIndex::Sixth,
+            Gua64YaoIndex::Fourth => Gua64YaoIndex::First,
+            Gua64YaoIndex::Fifth => Gua64YaoIndex::Second,
+            Gua64YaoIndex::Sixth => Gua64YaoIndex::Third,
+        }
+    }
+}
+
+/// 京房八宫中的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaGong {
+    /// 所属的宫，以八卦表示
+    pub gong: Gua8,
+    /// 世代
+    pub shi: GongShi,
+}
+
+impl BaGong {
+    pub const fn new(gong: Gua8, shi: GongShi) -> Self {
+        Self { gong, shi }
+    }
+
+    /// 查找卦所在的宫和世代
+    pub fn from_gua(gua: &Gua64) -> Self {
+        BA_GONG_LIST
+            .into_iter()
+            .flat_map(|gong| GongShi::ALL.map(|shi| BaGong::new(gong, shi)))
+            .find(|ba_gong| ba_gong.gua() == *gua)
+            .expect("八宫包含所有 64 卦")
+    }
+
+    /// 宫名，如 `乾宫`
+    pub fn name(&self) -> String {
+        format!("{}宫", self.gong.name())
+    }
+
+    /// 对应的六十四卦
+    pub fn gua(&self) -> Gua64 {
+        Gua64::new(self.gong, self.gong).changed(self.shi.dong_yao())
+    }
+
+    /// 宫中的八个卦，按世代排列
+    pub fn gong_list(gong: Gua8) -> [Gua64; 8] {
+        GongShi::ALL.map(|shi| BaGong::new(gong, shi).gua())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::core::models::{BaGong, Gua8, Gua64, Gua64YaoIndex};
+
+    use super::{BA_GONG_LIST, GongShi};
+
+    #[test]
+    /// 测试八宫排列
+    fn test_gong_list() {
+        assert_eq!(
+            BaGong::gong_list(Gua8::乾),
+            [
+                Gua64::乾,
+                Gua64::姤,
+                Gua64::遯,
+                Gua64::否,
+                Gua64::观,
+                Gua64::剥,
+                Gua64::晋,
+                Gua64::大有
+            ]
+        );
+
+        assert_eq!(
+            BaGong::gong_list(Gua8::坎),
+            [
+                Gua64::坎,
+                Gua64::节,
+                Gua64::屯,
+                Gua64::既济,
+                Gua64::革,
+                Gua64::丰,
+                Gua64::明夷,
+                Gua64::师
+            ]
+        );
+
+        // 八宫恰好包含全部 64 卦
+        let all: HashSet<String> = BA_GONG_LIST
+            .into_iter()
+            .flat_map(BaGong::gong_list)
+            .map(|gua| gua.id())
+            .collect();
+        assert_eq!(all.len(), 64);
+    }
+
+    #[test]
+    /// 测试世爻和应爻
+    fn test_shi_ying() {
+        let ba_gong = BaGong::from_gua(&Gua64::屯);
+        assert_eq!(ba_gong, BaGong::new(Gua8::坎, GongShi::二世));
+        assert_eq!(Gua64::屯.shi_yao(), Gua64YaoIndex::Second);
+        assert_eq!(Gua64::屯.ying_yao(), Gua64YaoIndex::Fifth);
+
+        assert_eq!(Gua64::乾.shi_yao(), Gua64YaoIndex::Sixth);
+        assert_eq!(Gua64::乾.ying_yao(), Gua64YaoIndex::Third);
+
+        assert_eq!(Gua64::晋.ba_gong().shi, GongShi::游魂);
+        assert_eq!(Gua64::晋.shi_yao(), Gua64YaoIndex::Fourth);
+        assert_eq!(Gua64::晋.ying_yao(), Gua64YaoIndex::First);
+
+        assert_eq!(Gua64::随.ba_gong(), BaGong::new(Gua8::震, GongShi::归魂));
+        assert_eq!(Gua64::随.shi_yao(), Gua64YaoIndex::Third);
+        assert_eq!(Gua64::随.ying_yao(), Gua64YaoIndex::Sixth);
+
+        for gua in Gua64::all() {
+            let ba_gong = gua.ba_gong();
+            assert_eq!(ba_gong.gua(), gua);
+
+            let shi = gua.shi_yao() as u8;
+            let ying = gua.ying_yao() as u8;
+            assert_eq!(shi.abs_diff(ying), 3);
+        }
+    }
+}
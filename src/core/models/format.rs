@@ -0,0 +1,39 @@
+use std::{error::Error, fmt::Display};
+
+/// 卦的文本格式，解析时以下格式都可以识别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuaFormat {
+    /// 卦名，如 `屯`、`坎`
+    Name,
+    /// 完整卦名，如 `水雷屯`；八卦为取象歌，如 `坎中满`
+    FullName,
+    /// Unicode 卦符，如 `䷂`、`☵`
+    Symbol,
+    /// 二进制编码，从初爻到上爻，阳为 `1`，阴为 `0`，如 `100010`、`010`
+    Binary,
+    /// 序号，六十四卦为文王卦序，八卦为先天数，如 `3`、`6`
+    Number,
+}
+
+/// 解析卦时的错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGuaError {
+    /// 输入为空
+    Empty,
+    /// 序号超出范围
+    OutOfRange(String),
+    /// 无法识别
+    Unknown(String),
+}
+
+impl Display for ParseGuaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGuaError::Empty => write!(f, "输入为空"),
+            ParseGuaError::OutOfRange(input) => write!(f, "序号超出范围：{input}"),
+            ParseGuaError::Unknown(input) => write!(f, "无法识别的卦：{input}"),
+        }
+    }
+}
+
+impl Error for ParseGuaError {}
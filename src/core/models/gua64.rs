@@ -1,12 +1,15 @@
 use crate::core::{models::Gua8YaoIndex, utils::ichang_mod};
 
-use super::{Gua8, Yao};
+use std::{fmt, str::FromStr};
+
+use super::{BaGong, Gua8, GuaFormat, ParseGuaError, Yao};
 use gpui::SharedString;
+use serde::{Deserialize, Serialize};
 
 /// 64 卦爻的顺序
 ///
 /// 注意爻的顺序是从下往上
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Gua64YaoIndex {
     /// 初爻（一爻）
     First = 1,
@@ -22,8 +25,32 @@ pub enum Gua64YaoIndex {
     Sixth,
 }
 
-impl From<u16> for Gua64YaoIndex {
-    fn from(value: u16) -> Self {
+impl Gua64YaoIndex {
+    /// 所有爻的位置，从初爻到上爻
+    pub const ALL: [Gua64YaoIndex; 6] = [
+        Gua64YaoIndex::First,
+        Gua64YaoIndex::Second,
+        Gua64YaoIndex::Third,
+        Gua64YaoIndex::Fourth,
+        Gua64YaoIndex::Fifth,
+        Gua64YaoIndex::Sixth,
+    ];
+
+    /// 爻位名称
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Gua64YaoIndex::First => "初爻",
+            Gua64YaoIndex::Second => "二爻",
+            Gua64YaoIndex::Third => "三爻",
+            Gua64YaoIndex::Fourth => "四爻",
+            Gua64YaoIndex::Fifth => "五爻",
+            Gua64YaoIndex::Sixth => "上爻",
+        }
+    }
+}
+
+impl From<u64> for Gua64YaoIndex {
+    fn from(value: u64) -> Self {
         let bian_index = ichang_mod(value, 6);
 
         match bian_index {
@@ -42,7 +69,7 @@ impl From<u16> for Gua64YaoIndex {
  * 六爻卦
  * 由 三爻卦 组合而成，共 64 种
  */
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Gua64 {
     /// 上卦
     shang: Gua8,
@@ -241,10 +268,153 @@ impl Gua64 {
     /// 未济
     pub const 未济: Gua64 = Gua64::new(Gua8::离, Gua8::坎);
 
+    /// 文王卦序（通行本《周易》卦序），第一个为乾，最后一个为未济
+    pub const KING_WEN: [Gua64; 64] = [
+        Gua64::乾,
+        Gua64::坤,
+        Gua64::屯,
+        Gua64::蒙,
+        Gua64::需,
+        Gua64::讼,
+        Gua64::师,
+        Gua64::比,
+        Gua64::小畜,
+        Gua64::履,
+        Gua64::泰,
+        Gua64::否,
+        Gua64::同人,
+        Gua64::大有,
+        Gua64::谦,
+        Gua64::豫,
+        Gua64::随,
+        Gua64::蛊,
+        Gua64::临,
+        Gua64::观,
+        Gua64::噬嗑,
+        Gua64::贲,
+        Gua64::剥,
+        Gua64::复,
+        Gua64::无妄,
+        Gua64::大畜,
+        Gua64::颐,
+        Gua64::大过,
+        Gua64::坎,
+        Gua64::离,
+        Gua64::咸,
+        Gua64::恒,
+        Gua64::遯,
+        Gua64::大壮,
+        Gua64::晋,
+        Gua64::明夷,
+        Gua64::家人,
+        Gua64::睽,
+        Gua64::蹇,
+        Gua64::解,
+        Gua64::损,
+        Gua64::益,
+        Gua64::夬,
+        Gua64::姤,
+        Gua64::萃,
+        Gua64::升,
+        Gua64::困,
+        Gua64::井,
+        Gua64::革,
+        Gua64::鼎,
+        Gua64::震,
+        Gua64::艮,
+        Gua64::渐,
+        Gua64::归妹,
+        Gua64::丰,
+        Gua64::旅,
+        Gua64::巽,
+        Gua64::兑,
+        Gua64::涣,
+        Gua64::节,
+        Gua64::中孚,
+        Gua64::小过,
+        Gua64::既济,
+        Gua64::未济,
+    ];
+
     pub const fn new(shang: Gua8, xia: Gua8) -> Self {
         Self { shang, xia }
     }
 
+    /// 按文王卦序遍历所有卦
+    pub fn all() -> impl Iterator<Item = Gua64> {
+        Self::KING_WEN.into_iter()
+    }
+
+    /// 按伏羲卦序（二进制数从 0 到 63）遍历所有卦
+    pub fn all_fu_xi() -> impl Iterator<Item = Gua64> {
+        (0..64).filter_map(Self::from_fu_xi_number)
+    }
+
+    /// 文王卦序，从 1 到 64
+    pub fn king_wen_number(&self) -> u8 {
+        let position = Self::KING_WEN
+            .iter()
+            .position(|gua| gua == self)
+            .expect("KING_WEN 包含所有 64 卦");
+
+        position as u8 + 1
+    }
+
+    /// 根据文王卦序获取卦，超出 1 到 64 时返回 `None`
+    pub fn from_king_wen_number(num: u8) -> Option<Self> {
+        match num {
+            1..=64 => Some(Self::KING_WEN[num as usize - 1].clone()),
+            _ => None,
+        }
+    }
+
+    /// 伏羲卦序，从 0 到 63
+    ///
+    /// 即把 `id` 当作二进制数，初爻为最高位，坤为 0，乾为 63
+    pub fn fu_xi_number(&self) -> u8 {
+        self.clone()
+            .into_iter()
+            .fold(0, |num, yao| (num << 1) | yao.is_yang() as u8)
+    }
+
+    /// 根据伏羲卦序获取卦，超出 0 到 63 时返回 `None`
+    pub fn from_fu_xi_number(num: u8) -> Option<Self> {
+        if num >= 64 {
+            return None;
+        }
+
+        // 初爻为最高位
+        let yao = |index: u8| Yao::new(num & (1 << (5 - index)) != 0);
+
+        let xia = Gua8::new(yao(0), yao(1), yao(2));
+        let shang = Gua8::new(yao(3), yao(4), yao(5));
+
+        Some(Self::new(shang, xia))
+    }
+
+    /// 根据二进制编码获取卦，编码格式同 [`Gua64::id`]
+    ///
+    /// 编码不是六位 `0`、`1` 时返回 `None`
+    pub fn from_id(id: &str) -> Option<Self> {
+        if id.len() != 6 || !id.chars().all(|c| c == '0' || c == '1') {
+            return None;
+        }
+
+        u8::from_str_radix(id, 2)
+            .ok()
+            .and_then(Self::from_fu_xi_number)
+    }
+
+    /// 上卦（外卦）
+    pub const fn shang(&self) -> Gua8 {
+        self.shang
+    }
+
+    /// 下卦（内卦）
+    pub const fn xia(&self) -> Gua8 {
+        self.xia
+    }
+
     /// 根据索引获取爻
     /// 从下往上数，共六个
     pub const fn yao(&self, index: Gua64YaoIndex) -> Yao {
@@ -279,6 +449,66 @@ impl Gua64 {
         Self::new(shang, xia)
     }
 
+    /// 获取错卦（旁通卦）
+    ///
+    /// 六个爻全部阴阳互换
+    pub const fn cuo_gua(&self) -> Self {
+        let shang = Gua8::new(
+            self.shang.first_yao.reversed(),
+            self.shang.second_yao.reversed(),
+            self.shang.third_yao.reversed(),
+        );
+
+        let xia = Gua8::new(
+            self.xia.first_yao.reversed(),
+            self.xia.second_yao.reversed(),
+            self.xia.third_yao.reversed(),
+        );
+
+        Self::new(shang, xia)
+    }
+
+    /// 获取综卦（反卦）
+    ///
+    /// 将卦上下颠倒，上爻变为初爻
+    pub const fn zong_gua(&self) -> Self {
+        let shang = Gua8::new(
+            self.yao(Gua64YaoIndex::Third),
+            self.yao(Gua64YaoIndex::Second),
+            self.yao(Gua64YaoIndex::First),
+        );
+
+        let xia = Gua8::new(
+            self.yao(Gua64YaoIndex::Sixth),
+            self.yao(Gua64YaoIndex::Fifth),
+            self.yao(Gua64YaoIndex::Fourth),
+        );
+
+        Self::new(shang, xia)
+    }
+
+    /// 获取交卦
+    ///
+    /// 上卦和下卦互换位置
+    pub const fn jiao_gua(&self) -> Self {
+        Self::new(self.xia, self.shang)
+    }
+
+    /// 京房八宫中所属的宫和世代
+    pub fn ba_gong(&self) -> BaGong {
+        BaGong::from_gua(self)
+    }
+
+    /// 世爻
+    pub fn shi_yao(&self) -> Gua64YaoIndex {
+        self.ba_gong().shi.shi_yao()
+    }
+
+    /// 应爻
+    pub fn ying_yao(&self) -> Gua64YaoIndex {
+        self.ba_gong().shi.ying_yao()
+    }
+
     /// 获得卦象名称
     pub const fn name(&self) -> &'static str {
         match (self.shang, self.xia) {
@@ -349,6 +579,47 @@ impl Gua64 {
         }
     }
 
+    /// 完整卦名，由上下卦的自然象加卦名组成，如 `水雷屯`、`地天泰`
+    ///
+    /// 上下卦相同时为 `乾为天`、`坎为水` 的形式
+    pub fn full_name(&self) -> String {
+        match self.shang == self.xia {
+            true => format!("{}为{}", self.name(), self.shang.zi_ran()),
+            false => format!(
+                "{}{}{}",
+                self.shang.zi_ran(),
+                self.xia.zi_ran(),
+                self.name()
+            ),
+        }
+    }
+
+    /// Unicode 卦符，按文王卦序从 `䷀` 排到 `䷿`
+    pub fn symbol(&self) -> char {
+        char::from_u32(0x4DC0 + self.king_wen_number() as u32 - 1).unwrap_or_default()
+    }
+
+    /// 按指定格式输出
+    pub fn format(&self, format: GuaFormat) -> String {
+        match format {
+            GuaFormat::Name => self.name().to_string(),
+            GuaFormat::FullName => self.full_name(),
+            GuaFormat::Symbol => self.symbol().to_string(),
+            GuaFormat::Binary => self.id(),
+            GuaFormat::Number => self.king_wen_number().to_string(),
+        }
+    }
+
+    /// 二进制编码，从初爻到上爻，阳为 `1`，阴为 `0`
+    ///
+    /// 和 `Gua64Info.id` 一致，例如屯卦为 `100010`
+    pub fn id(&self) -> String {
+        self.clone()
+            .into_iter()
+            .map(|yao| if yao.is_yang() { '1' } else { '0' })
+            .collect()
+    }
+
     /// 进行变卦
     ///
     /// * `index` - Gua64YaoIndex，要改变的爻
@@ -362,6 +633,73 @@ impl Gua64 {
             Gua64YaoIndex::Sixth => self.shang.reverse(Gua8YaoIndex::Third),
         }
     }
+
+    /// 变动多个爻，返回新的卦
+    ///
+    /// * `indexes` - 要改变的爻
+    pub fn changed(&self, indexes: &[Gua64YaoIndex]) -> Self {
+        let mut gua = self.clone();
+
+        for index in indexes {
+            gua.change(*index);
+        }
+
+        gua
+    }
+}
+
+impl fmt::Display for Gua64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Gua64 {
+    type Err = ParseGuaError;
+
+    /// 可以识别卦名、完整卦名、卦符、二进制编码和文王卦序
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.trim();
+
+        if input.is_empty() {
+            return Err(ParseGuaError::Empty);
+        }
+
+        if let Some(gua) = Gua64::from_id(input) {
+            return Ok(gua);
+        }
+
+        if input.chars().all(|c| c.is_ascii_digit()) {
+            return input
+                .parse::<u8>()
+                .map_err(|_| ParseGuaError::OutOfRange(input.into()))
+                .and_then(Gua64::try_from);
+        }
+
+        Gua64::all()
+            .find(|gua| {
+                gua.name() == input || gua.full_name() == input || gua.symbol().to_string() == input
+            })
+            .ok_or_else(|| ParseGuaError::Unknown(input.into()))
+    }
+}
+
+impl TryFrom<&str> for Gua64 {
+    type Error = ParseGuaError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<u8> for Gua64 {
+    type Error = ParseGuaError;
+
+    /// 按文王卦序获取卦
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Gua64::from_king_wen_number(value)
+            .ok_or_else(|| ParseGuaError::OutOfRange(value.to_string()))
+    }
 }
 
 pub struct Gua64Iterator {
@@ -402,3 +740,222 @@ impl IntoIterator for Gua64 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{
+        assets::init_gua64_info,
+        core::models::{Gua64, GuaFormat, ParseGuaError},
+    };
+
+    #[test]
+    /// 测试文王卦序
+    fn test_king_wen_number() {
+        assert_eq!(Gua64::乾.king_wen_number(), 1);
+        assert_eq!(Gua64::屯.king_wen_number(), 3);
+        assert_eq!(Gua64::未济.king_wen_number(), 64);
+
+        assert_eq!(Gua64::from_king_wen_number(0), None);
+        assert_eq!(Gua64::from_king_wen_number(65), None);
+
+        for num in 1..=64 {
+            let gua = Gua64::from_king_wen_number(num).unwrap();
+            assert_eq!(gua.king_wen_number(), num);
+        }
+
+        // 资源文件按文王卦序排列
+        for (info, gua) in init_gua64_info().unwrap().iter().zip(Gua64::all()) {
+            assert_eq!(info.id, gua.id());
+            assert_eq!(info.name, gua.name());
+        }
+    }
+
+    #[test]
+    /// 测试伏羲卦序以及二进制编码
+    fn test_fu_xi_number() {
+        assert_eq!(Gua64::坤.fu_xi_number(), 0);
+        assert_eq!(Gua64::乾.fu_xi_number(), 63);
+        assert_eq!(Gua64::屯.fu_xi_number(), 0b100010);
+
+        assert_eq!(Gua64::from_fu_xi_number(64), None);
+
+        for num in 0..64 {
+            let gua = Gua64::from_fu_xi_number(num).unwrap();
+            assert_eq!(gua.fu_xi_number(), num);
+            assert_eq!(Gua64::from_id(&gua.id()), Some(gua));
+        }
+
+        assert_eq!(Gua64::from_id("100010"), Some(Gua64::屯));
+        assert_eq!(Gua64::from_id("10001"), None);
+        assert_eq!(Gua64::from_id("100012"), None);
+        assert_eq!(Gua64::from_id("+10001"), None);
+    }
+
+    #[test]
+    /// 测试错卦、综卦、交卦
+    fn test_cuo_zong_jiao() {
+        assert_eq!(Gua64::屯.cuo_gua(), Gua64::鼎);
+        assert_eq!(Gua64::屯.zong_gua(), Gua64::蒙);
+        assert_eq!(Gua64::屯.jiao_gua(), Gua64::解);
+
+        assert_eq!(Gua64::乾.cuo_gua(), Gua64::坤);
+        assert_eq!(Gua64::乾.zong_gua(), Gua64::乾);
+        assert_eq!(Gua64::泰.jiao_gua(), Gua64::否);
+
+        for gua in Gua64::all() {
+            let id = gua.id();
+
+            // 错卦：每一位取反
+            let cuo_id: String = id
+                .chars()
+                .map(|c| if c == '1' { '0' } else { '1' })
+                .collect();
+            assert_eq!(gua.cuo_gua().id(), cuo_id);
+
+            // 综卦：顺序颠倒
+            let zong_id: String = id.chars().rev().collect();
+            assert_eq!(gua.zong_gua().id(), zong_id);
+
+            // 交卦：前三位和后三位互换
+            let jiao_id = format!("{}{}", &id[3..], &id[..3]);
+            assert_eq!(gua.jiao_gua().id(), jiao_id);
+
+            // 再做一次回到本身
+            assert_eq!(gua.cuo_gua().cuo_gua(), gua);
+            assert_eq!(gua.zong_gua().zong_gua(), gua);
+            assert_eq!(gua.jiao_gua().jiao_gua(), gua);
+        }
+    }
+
+    #[test]
+    /// 两种顺序都包含全部 64 卦
+    fn test_all() {
+        let king_wen: HashSet<String> = Gua64::all().map(|gua| gua.id()).collect();
+        let fu_xi: Vec<u8> = Gua64::all_fu_xi().map(|gua| gua.fu_xi_number()).collect();
+
+        assert_eq!(king_wen.len(), 64);
+        assert_eq!(fu_xi, (0..64).collect::<Vec<_>>());
+    }
+
+    #[test]
+    /// 测试完整卦名，按文王卦序
+    fn test_full_name() {
+        let full_names = [
+            "乾为天",
+            "坤为地",
+            "水雷屯",
+            "山水蒙",
+            "水天需",
+            "天水讼",
+            "地水师",
+            "水地比",
+            "风天小畜",
+            "天泽履",
+            "地天泰",
+            "天地否",
+            "天火同人",
+            "火天大有",
+            "地山谦",
+            "雷地豫",
+            "泽雷随",
+            "山风蛊",
+            "地泽临",
+            "风地观",
+            "火雷噬嗑",
+            "山火贲",
+            "山地剥",
+            "地雷复",
+            "天雷无妄",
+            "山天大畜",
+            "山雷颐",
+            "泽风大过",
+            "坎为水",
+            "离为火",
+            "泽山咸",
+            "雷风恒",
+            "天山遯",
+            "雷天大壮",
+            "火地晋",
+            "地火明夷",
+            "风火家人",
+            "火泽睽",
+            "水山蹇",
+            "雷水解",
+            "山泽损",
+            "风雷益",
+            "泽天夬",
+            "天风姤",
+            "泽地萃",
+            "地风升",
+            "泽水困",
+            "水风井",
+            "泽火革",
+            "火风鼎",
+            "震为雷",
+            "艮为山",
+            "风山渐",
+            "雷泽归妹",
+            "雷火丰",
+            "火山旅",
+            "巽为风",
+            "兑为泽",
+            "风水涣",
+            "水泽节",
+            "风泽中孚",
+            "雷山小过",
+            "水火既济",
+            "火水未济",
+        ];
+
+        let result: Vec<String> = Gua64::all().map(|gua| gua.full_name()).collect();
+
+        assert_eq!(result, full_names);
+    }
+
+    #[test]
+    /// 测试各种格式的输出和解析
+    fn test_parse() {
+        let formats = [
+            GuaFormat::Name,
+            GuaFormat::FullName,
+            GuaFormat::Symbol,
+            GuaFormat::Binary,
+            GuaFormat::Number,
+        ];
+
+        for gua in Gua64::all() {
+            for format in formats {
+                assert_eq!(gua.format(format).parse::<Gua64>(), Ok(gua.clone()));
+            }
+        }
+
+        for input in ["屯", "水雷屯", "䷂", "100010", "3", " 3 "] {
+            assert_eq!(Gua64::try_from(input), Ok(Gua64::屯));
+        }
+
+        assert_eq!(Gua64::乾.symbol(), '䷀');
+        assert_eq!(Gua64::未济.symbol(), '䷿');
+        assert_eq!(Gua64::屯.to_string(), "屯");
+        assert_eq!(Gua64::try_from(64), Ok(Gua64::未济));
+
+        assert_eq!(" ".parse::<Gua64>(), Err(ParseGuaError::Empty));
+        assert_eq!(
+            "65".parse::<Gua64>(),
+            Err(ParseGuaError::OutOfRange("65".into()))
+        );
+        assert_eq!(
+            "1000".parse::<Gua64>(),
+            Err(ParseGuaError::OutOfRange("1000".into()))
+        );
+        assert_eq!(
+            "10001".parse::<Gua64>(),
+            Err(ParseGuaError::OutOfRange("10001".into()))
+        );
+        assert_eq!(
+            "天".parse::<Gua64>(),
+            Err(ParseGuaError::Unknown("天".into()))
+        );
+    }
+}
@@ -1,4 +1,10 @@
-use super::Yao;
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use super::{GuaFormat, ParseGuaError, Yao};
+use crate::core::{error::CoreError, gan_zhi::WuXing};
 
 /// 八卦爻的顺序
 /// 注意爻的顺序是从下往上
@@ -11,12 +17,205 @@ pub enum Gua8YaoIndex {
     Third,
 }
 
+/// 方位
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display)]
+pub enum FangWei {
+    东,
+    南,
+    西,
+    北,
+    东南,
+    西南,
+    东北,
+    西北,
+}
+
+/// 自然象
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display)]
+pub enum ZiRan {
+    天,
+    泽,
+    火,
+    雷,
+    风,
+    水,
+    山,
+    地,
+}
+
+/// 家人
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display)]
+pub enum JiaRen {
+    父,
+    母,
+    长男,
+    长女,
+    中男,
+    中女,
+    少男,
+    少女,
+}
+
+/// 身体
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display)]
+pub enum ShenTi {
+    首,
+    腹,
+    足,
+    股,
+    耳,
+    目,
+    手,
+    口,
+}
+
+/// 动物
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display)]
+pub enum DongWu {
+    马,
+    牛,
+    龙,
+    鸡,
+    豕,
+    雉,
+    狗,
+    羊,
+}
+
+/// 颜色
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display)]
+pub enum YanSe {
+    白,
+    赤,
+    青,
+    绿,
+    黑,
+    黄,
+}
+
+/// 卦德
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display)]
+pub enum GuaDe {
+    健,
+    顺,
+    动,
+    入,
+    陷,
+    丽,
+    止,
+    说,
+}
+
+// 以下类象均按先天数排列，乾为第一个
+
+/// 自然象
+const ZI_RAN: [ZiRan; 8] = [
+    ZiRan::天,
+    ZiRan::泽,
+    ZiRan::火,
+    ZiRan::雷,
+    ZiRan::风,
+    ZiRan::水,
+    ZiRan::山,
+    ZiRan::地,
+];
+/// 后天数
+const HOU_TIAN_NUMBER: [u8; 8] = [6, 7, 9, 3, 4, 1, 8, 2];
+/// 先天方位
+const XIAN_TIAN_FANG_WEI: [FangWei; 8] = [
+    FangWei::南,
+    FangWei::东南,
+    FangWei::东,
+    FangWei::东北,
+    FangWei::西南,
+    FangWei::西,
+    FangWei::西北,
+    FangWei::北,
+];
+/// 后天方位
+const HOU_TIAN_FANG_WEI: [FangWei; 8] = [
+    FangWei::西北,
+    FangWei::西,
+    FangWei::南,
+    FangWei::东,
+    FangWei::东南,
+    FangWei::北,
+    FangWei::东北,
+    FangWei::西南,
+];
+/// 家人
+const JIA_REN: [JiaRen; 8] = [
+    JiaRen::父,
+    JiaRen::少女,
+    JiaRen::中女,
+    JiaRen::长男,
+    JiaRen::长女,
+    JiaRen::中男,
+    JiaRen::少男,
+    JiaRen::母,
+];
+/// 身体
+const SHEN_TI: [ShenTi; 8] = [
+    ShenTi::首,
+    ShenTi::口,
+    ShenTi::目,
+    ShenTi::足,
+    ShenTi::股,
+    ShenTi::耳,
+    ShenTi::手,
+    ShenTi::腹,
+];
+/// 动物
+const DONG_WU: [DongWu; 8] = [
+    DongWu::马,
+    DongWu::羊,
+    DongWu::雉,
+    DongWu::龙,
+    DongWu::鸡,
+    DongWu::豕,
+    DongWu::狗,
+    DongWu::牛,
+];
+/// 颜色
+const YAN_SE: [YanSe; 8] = [
+    YanSe::白,
+    YanSe::白,
+    YanSe::赤,
+    YanSe::青,
+    YanSe::绿,
+    YanSe::黑,
+    YanSe::黄,
+    YanSe::黄,
+];
+/// 八卦取象歌
+const QU_XIANG: [&str; 8] = [
+    "乾三连",
+    "兑上缺",
+    "离中虚",
+    "震仰盂",
+    "巽下断",
+    "坎中满",
+    "艮覆碗",
+    "坤六断",
+];
+/// 卦德
+const GUA_DE: [GuaDe; 8] = [
+    GuaDe::健,
+    GuaDe::说,
+    GuaDe::丽,
+    GuaDe::动,
+    GuaDe::入,
+    GuaDe::陷,
+    GuaDe::止,
+    GuaDe::顺,
+];
+
 /// 八卦
 /// 三个爻为一个卦，共有八个，即八卦
 ///
 /// # 注意
 /// 爻的顺序是从下到上的，所以初爻是最下面的
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Gua8 {
     /// 初爻，即一爻
     pub first_yao: Yao,
@@ -48,6 +247,18 @@ impl Gua8 {
     /// 坤
     pub const 坤: Gua8 = Gua8::new(Yao::阴, Yao::阴, Yao::阴);
 
+    /// 所有八卦，按先天数排列
+    pub const ALL: [Gua8; 8] = [
+        Gua8::乾,
+        Gua8::兑,
+        Gua8::离,
+        Gua8::震,
+        Gua8::巽,
+        Gua8::坎,
+        Gua8::艮,
+        Gua8::坤,
+    ];
+
     /// 根据三个爻创建新的八卦
     ///
     /// # 注意
@@ -76,21 +287,125 @@ impl Gua8 {
         name
     }
 
-    /// 解析数字，返回 BaGua
-    pub fn from_num(num: u8) -> Self {
-        let result = match num {
-            1 => Gua8::乾,
-            2 => Gua8::兑,
-            3 => Gua8::离,
-            4 => Gua8::震,
-            5 => Gua8::巽,
-            6 => Gua8::坎,
-            7 => Gua8::艮,
-            8 => Gua8::坤,
-            _ => !unreachable!(),
-        };
+    /// Unicode 卦符
+    pub const fn symbol(&self) -> &'static str {
+        match (self.first_yao, self.second_yao, self.third_yao) {
+            (Yao::阳, Yao::阳, Yao::阳) => "☰",
+            (Yao::阳, Yao::阳, Yao::阴) => "☱",
+            (Yao::阳, Yao::阴, Yao::阳) => "☲",
+            (Yao::阳, Yao::阴, Yao::阴) => "☳",
+            (Yao::阴, Yao::阳, Yao::阳) => "☴",
+            (Yao::阴, Yao::阳, Yao::阴) => "☵",
+            (Yao::阴, Yao::阴, Yao::阳) => "☶",
+            (Yao::阴, Yao::阴, Yao::阴) => "☷",
+        }
+    }
+
+    /// 五行
+    pub const fn wu_xing(&self) -> WuXing {
+        match (self.first_yao, self.second_yao, self.third_yao) {
+            (Yao::阳, Yao::阳, Yao::阳) | (Yao::阳, Yao::阳, Yao::阴) => WuXing::金,
+            (Yao::阳, Yao::阴, Yao::阳) => WuXing::火,
+            (Yao::阳, Yao::阴, Yao::阴) | (Yao::阴, Yao::阳, Yao::阳) => WuXing::木,
+            (Yao::阴, Yao::阳, Yao::阴) => WuXing::水,
+            (Yao::阴, Yao::阴, Yao::阳) | (Yao::阴, Yao::阴, Yao::阴) => WuXing::土,
+        }
+    }
+
+    /// 先天数：乾一、兑二、离三、震四、巽五、坎六、艮七、坤八
+    pub const fn xian_tian_number(&self) -> u8 {
+        match (self.first_yao, self.second_yao, self.third_yao) {
+            (Yao::阳, Yao::阳, Yao::阳) => 1,
+            (Yao::阳, Yao::阳, Yao::阴) => 2,
+            (Yao::阳, Yao::阴, Yao::阳) => 3,
+            (Yao::阳, Yao::阴, Yao::阴) => 4,
+            (Yao::阴, Yao::阳, Yao::阳) => 5,
+            (Yao::阴, Yao::阳, Yao::阴) => 6,
+            (Yao::阴, Yao::阴, Yao::阳) => 7,
+            (Yao::阴, Yao::阴, Yao::阴) => 8,
+        }
+    }
+
+    /// 在类象表中的下标
+    const fn index(&self) -> usize {
+        self.xian_tian_number() as usize - 1
+    }
+
+    /// 自然象：乾天、兑泽、离火、震雷、巽风、坎水、艮山、坤地
+    pub const fn zi_ran(&self) -> ZiRan {
+        ZI_RAN[self.index()]
+    }
+
+    /// 后天数，即洛书九宫数：坎一、坤二、震三、巽四、乾六、兑七、艮八、离九
+    pub const fn hou_tian_number(&self) -> u8 {
+        HOU_TIAN_NUMBER[self.index()]
+    }
+
+    /// 先天八卦方位，乾南坤北、离东坎西
+    pub const fn xian_tian_fang_wei(&self) -> FangWei {
+        XIAN_TIAN_FANG_WEI[self.index()]
+    }
+
+    /// 后天八卦方位，离南坎北、震东兑西
+    pub const fn hou_tian_fang_wei(&self) -> FangWei {
+        HOU_TIAN_FANG_WEI[self.index()]
+    }
+
+    /// 家人，出自《说卦传》：乾为父，坤为母，震、坎、艮为三男，巽、离、兑为三女
+    pub const fn jia_ren(&self) -> JiaRen {
+        JIA_REN[self.index()]
+    }
+
+    /// 身体，出自《说卦传》：乾为首，坤为腹，震为足，巽为股，坎为耳，离为目，艮为手，兑为口
+    pub const fn shen_ti(&self) -> ShenTi {
+        SHEN_TI[self.index()]
+    }
+
+    /// 动物，出自《说卦传》：乾为马，坤为牛，震为龙，巽为鸡，坎为豕，离为雉，艮为狗，兑为羊
+    pub const fn dong_wu(&self) -> DongWu {
+        DONG_WU[self.index()]
+    }
+
+    /// 颜色，按梅花易数的类象
+    pub const fn yan_se(&self) -> YanSe {
+        YAN_SE[self.index()]
+    }
+
+    /// 卦德，出自《说卦传》：乾健也，坤顺也，震动也，巽入也，坎陷也，离丽也，艮止也，兑说也
+    pub const fn gua_de(&self) -> GuaDe {
+        GUA_DE[self.index()]
+    }
+
+    /// 八卦取象歌中的说法，如 `乾三连`、`坎中满`
+    pub const fn qu_xiang_ge(&self) -> &'static str {
+        QU_XIANG[self.index()]
+    }
 
-        result
+    /// 二进制编码，从初爻到三爻，阳为 `1`，阴为 `0`，例如坎卦为 `010`
+    pub fn id(&self) -> String {
+        [self.first_yao, self.second_yao, self.third_yao]
+            .iter()
+            .map(|yao| if yao.is_yang() { '1' } else { '0' })
+            .collect()
+    }
+
+    /// 按指定格式输出
+    pub fn format(&self, format: GuaFormat) -> String {
+        match format {
+            GuaFormat::Name => self.name().to_string(),
+            GuaFormat::FullName => self.qu_xiang_ge().to_string(),
+            GuaFormat::Symbol => self.symbol().to_string(),
+            GuaFormat::Binary => self.id(),
+            GuaFormat::Number => self.xian_tian_number().to_string(),
+        }
+    }
+
+    /// 根据先天数获取八卦，乾为 1，坤为 8
+    pub fn from_num(num: u8) -> Result<Self, CoreError> {
+        match num {
+            1..=8 => Ok(Gua8::ALL[num as usize - 1]),
+            _ => Err(CoreError::Gua8Number(num)),
+        }
     }
 
     ///翻转指定位置的爻
@@ -103,9 +418,72 @@ impl Gua8 {
     }
 }
 
+impl fmt::Display for Gua8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Gua8 {
+    type Err = ParseGuaError;
+
+    /// 可以识别卦名、自然象、取象歌、卦符、二进制编码和先天数
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let input = s.trim();
+
+        if input.is_empty() {
+            return Err(ParseGuaError::Empty);
+        }
+
+        if input.len() == 3 && input.chars().all(|c| c == '0' || c == '1') {
+            return Gua8::ALL
+                .into_iter()
+                .find(|gua| gua.id() == input)
+                .ok_or_else(|| ParseGuaError::Unknown(input.into()));
+        }
+
+        if input.chars().all(|c| c.is_ascii_digit()) {
+            return input
+                .parse::<u8>()
+                .map_err(|_| ParseGuaError::OutOfRange(input.into()))
+                .and_then(Gua8::try_from);
+        }
+
+        Gua8::ALL
+            .into_iter()
+            .find(|gua| {
+                [gua.name(), gua.qu_xiang_ge(), gua.symbol()].contains(&input)
+                    || gua.zi_ran().to_string() == input
+            })
+            .ok_or_else(|| ParseGuaError::Unknown(input.into()))
+    }
+}
+
+impl TryFrom<&str> for Gua8 {
+    type Error = ParseGuaError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<u8> for Gua8 {
+    type Error = ParseGuaError;
+
+    /// 按先天数获取八卦，乾为 1，坤为 8
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Gua8::from_num(value).map_err(|_| ParseGuaError::OutOfRange(value.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::core::models::{Gua8, Gua8YaoIndex, Yao};
+    use crate::core::{
+        error::CoreError,
+        models::{FangWei, Gua8, Gua8YaoIndex, GuaFormat, ParseGuaError, Yao},
+    };
+
+    use super::{JiaRen, ZiRan};
 
     #[test]
     /// 测试变卦功能
@@ -145,4 +523,99 @@ mod tests {
         gua.reverse(Gua8YaoIndex::First);
         assert_eq!(gua, Gua8::离);
     }
+
+    #[test]
+    /// 测试八卦类象
+    fn test_lei_xiang() {
+        for (index, gua) in Gua8::ALL.into_iter().enumerate() {
+            assert_eq!(gua.xian_tian_number() as usize, index + 1);
+            assert_eq!(Gua8::from_num(gua.xian_tian_number()).unwrap(), gua);
+        }
+
+        let zi_ran: Vec<_> = Gua8::ALL.iter().map(|gua| gua.zi_ran()).collect();
+        assert_eq!(
+            zi_ran,
+            [
+                ZiRan::天,
+                ZiRan::泽,
+                ZiRan::火,
+                ZiRan::雷,
+                ZiRan::风,
+                ZiRan::水,
+                ZiRan::山,
+                ZiRan::地
+            ]
+        );
+
+        // 后天数除去中五，刚好是 1 到 9
+        let mut hou_tian: Vec<_> = Gua8::ALL.iter().map(|gua| gua.hou_tian_number()).collect();
+        hou_tian.sort();
+        assert_eq!(hou_tian, [1, 2, 3, 4, 6, 7, 8, 9]);
+
+        // 先天、后天方位各不相同，且两两相对的卦方位相对
+        assert_eq!(Gua8::乾.xian_tian_fang_wei(), FangWei::南);
+        assert_eq!(Gua8::坤.xian_tian_fang_wei(), FangWei::北);
+        assert_eq!(Gua8::离.hou_tian_fang_wei(), FangWei::南);
+        assert_eq!(Gua8::坎.hou_tian_fang_wei(), FangWei::北);
+        assert_eq!(Gua8::艮.hou_tian_fang_wei(), FangWei::东北);
+
+        // 乾坤为父母，其余六卦各有三男三女
+        assert_eq!(Gua8::乾.jia_ren(), JiaRen::父);
+        assert_eq!(Gua8::坤.jia_ren(), JiaRen::母);
+        assert_eq!(Gua8::震.jia_ren(), JiaRen::长男);
+        assert_eq!(Gua8::兑.jia_ren(), JiaRen::少女);
+
+        assert_eq!(Gua8::离.shen_ti().to_string(), "目");
+        assert_eq!(Gua8::坎.dong_wu().to_string(), "豕");
+        assert_eq!(Gua8::兑.gua_de().to_string(), "说");
+    }
+
+    #[test]
+    /// 测试各种格式的输出和解析
+    fn test_parse() {
+        let formats = [
+            GuaFormat::Name,
+            GuaFormat::FullName,
+            GuaFormat::Symbol,
+            GuaFormat::Binary,
+            GuaFormat::Number,
+        ];
+
+        for gua in Gua8::ALL {
+            for format in formats {
+                assert_eq!(gua.format(format).parse::<Gua8>(), Ok(gua));
+            }
+
+            assert_eq!(gua.zi_ran().to_string().parse::<Gua8>(), Ok(gua));
+        }
+
+        assert_eq!(Gua8::坎.format(GuaFormat::Binary), "010");
+        assert_eq!(Gua8::坎.format(GuaFormat::FullName), "坎中满");
+        assert_eq!(Gua8::坎.to_string(), "坎");
+        assert_eq!(Gua8::try_from(" 6 "), Ok(Gua8::坎));
+        assert_eq!(Gua8::try_from(7), Ok(Gua8::艮));
+
+        assert_eq!("".parse::<Gua8>(), Err(ParseGuaError::Empty));
+        assert_eq!(
+            "9".parse::<Gua8>(),
+            Err(ParseGuaError::OutOfRange("9".into()))
+        );
+        assert_eq!(
+            Gua8::try_from(0),
+            Err(ParseGuaError::OutOfRange("0".into()))
+        );
+        assert_eq!(
+            "屯".parse::<Gua8>(),
+            Err(ParseGuaError::Unknown("屯".into()))
+        );
+    }
+
+    #[test]
+    /// 先天数超出范围时返回错误
+    fn test_from_num() {
+        assert_eq!(Gua8::from_num(1).unwrap(), Gua8::乾);
+        assert_eq!(Gua8::from_num(8).unwrap(), Gua8::坤);
+        assert!(matches!(Gua8::from_num(0), Err(CoreError::Gua8Number(0))));
+        assert!(matches!(Gua8::from_num(9), Err(CoreError::Gua8Number(9))));
+    }
 }
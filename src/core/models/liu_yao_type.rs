@@ -0,0 +1,185 @@
+use std::{error::Error, fmt};
+
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString, IntoStaticStr};
+
+use super::Yao;
+use crate::core::yao_gua::CoinToss;
+
+/// 爻的类型
+///
+/// 用于六爻起卦，所以需要四种
+#[derive(
+    Debug, PartialEq, Eq, EnumString, Display, Clone, Copy, IntoStaticStr, Serialize, Deserialize,
+)]
+pub enum LiuYaoType {
+    /// 阴
+    #[strum(serialize = "阴")]
+    阴,
+    /// 阳
+    #[strum(serialize = "阳")]
+    阳,
+    /// 动阴
+    #[strum(serialize = "动阴")]
+    动阴,
+    /// 动阳
+    #[strum(serialize = "动阳")]
+    动阳,
+}
+
+impl LiuYaoType {
+    /// 转换为 Yao
+    ///
+    /// * `阴` 和 `动阴` 转为 `阴`
+    /// * `阳` 和 `动阳` 转为 `阳`
+    pub const fn yao(self) -> Yao {
+        match self {
+            LiuYaoType::阴 | LiuYaoType::动阴 => Yao::阴,
+            LiuYaoType::阳 | LiuYaoType::动阳 => Yao::阳,
+        }
+    }
+
+    /// 是否是动幺
+    pub fn is_dong(self) -> bool {
+        matches!(self, LiuYaoType::动阴 | LiuYaoType::动阳)
+    }
+
+    /// 从六、七、八、九转换
+    ///
+    /// 六为老阴，七为少阳，八为少阴，九为老阳
+    pub const fn from_number(num: u8) -> Option<Self> {
+        match num {
+            6 => Some(LiuYaoType::动阴),
+            7 => Some(LiuYaoType::阳),
+            8 => Some(LiuYaoType::阴),
+            9 => Some(LiuYaoType::动阳),
+            _ => None,
+        }
+    }
+
+    /// 从三枚铜钱中字（正面）的数量转换
+    ///
+    /// 字记二，背记三，所以三枚相加为 `9 - 字的数量`
+    pub const fn from_heads(count: u8) -> Option<Self> {
+        match count {
+            0..=3 => Self::from_number(9 - count),
+            _ => None,
+        }
+    }
+
+    /// 解析六个爻，从初爻到上爻
+    ///
+    /// 支持两种写法，可以用空格、逗号分隔：
+    /// * 六、七、八、九，如 `789667`
+    /// * 每次投掷中字的数量 0 到 3，如 `210332`
+    pub fn parse_list(input: &str) -> Result<[LiuYaoType; 6], ParseLiuYaoError> {
+        let digits: Vec<char> = input
+            .chars()
+            .filter(|c| !c.is_whitespace() && !matches!(c, ',' | '，' | '、'))
+            .collect();
+
+        if digits.is_empty() {
+            return Err(ParseLiuYaoError::Empty);
+        }
+
+        if let Some(c) = digits.iter().find(|c| !matches!(c, '0'..='3' | '6'..='9')) {
+            return Err(ParseLiuYaoError::InvalidChar(*c));
+        }
+
+        if digits.len() != 6 {
+            return Err(ParseLiuYaoError::Length(digits.len()));
+        }
+
+        let numbers: Vec<u8> = digits
+            .iter()
+            .filter_map(|c| c.to_digit(10))
+            .map(|num| num as u8)
+            .collect();
+
+        let convert: fn(u8) -> Option<Self> = match (
+            numbers.iter().all(|num| *num >= 6),
+            numbers.iter().all(|num| *num <= 3),
+        ) {
+            (true, _) => Self::from_number,
+            (_, true) => Self::from_heads,
+            _ => return Err(ParseLiuYaoError::MixedNotation),
+        };
+
+        let list: Vec<Self> = numbers.into_iter().filter_map(convert).collect();
+
+        list.try_into()
+            .map_err(|list: Vec<Self>| ParseLiuYaoError::Length(list.len()))
+    }
+
+    /// 随机生成 LiuYaoType，即掷一次三枚铜钱
+    pub fn random() -> Self {
+        CoinToss::random().liu_yao_type()
+    }
+}
+
+/// 解析六爻输入时的错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseLiuYaoError {
+    /// 输入为空
+    Empty,
+    /// 不是六位
+    Length(usize),
+    /// 包含无法识别的字符
+    InvalidChar(char),
+    /// 六七八九和字的数量混用
+    MixedNotation,
+}
+
+impl fmt::Display for ParseLiuYaoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseLiuYaoError::Empty => write!(f, "请输入六位数字"),
+            ParseLiuYaoError::Length(len) => write!(f, "需要六位数字，实际为 {len} 位"),
+            ParseLiuYaoError::InvalidChar(c) => {
+                write!(f, "无法识别“{c}”，只能输入 6 到 9，或字的数量 0 到 3")
+            }
+            ParseLiuYaoError::MixedNotation => {
+                write!(f, "6 到 9 与字的数量 0 到 3 不能混用")
+            }
+        }
+    }
+}
+
+impl Error for ParseLiuYaoError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{LiuYaoType, ParseLiuYaoError};
+
+    #[test]
+    /// 测试解析六爻输入
+    fn test_parse_list() {
+        use LiuYaoType::*;
+
+        let expected = [阳, 阴, 动阳, 动阴, 动阴, 阳];
+
+        assert_eq!(LiuYaoType::parse_list("789667"), Ok(expected));
+        assert_eq!(LiuYaoType::parse_list("7 8 9 6 6 7"), Ok(expected));
+        assert_eq!(LiuYaoType::parse_list("7,8，9、6 6 7"), Ok(expected));
+        // 字的数量：两字为七，一字为八，无字为九，三字为六
+        assert_eq!(LiuYaoType::parse_list("210332"), Ok(expected));
+
+        assert_eq!(LiuYaoType::parse_list(" "), Err(ParseLiuYaoError::Empty));
+        assert_eq!(
+            LiuYaoType::parse_list("78966"),
+            Err(ParseLiuYaoError::Length(5))
+        );
+        assert_eq!(
+            LiuYaoType::parse_list("7896675"),
+            Err(ParseLiuYaoError::InvalidChar('5'))
+        );
+        assert_eq!(
+            LiuYaoType::parse_list("78a667"),
+            Err(ParseLiuYaoError::InvalidChar('a'))
+        );
+        assert_eq!(
+            LiuYaoType::parse_list("789001"),
+            Err(ParseLiuYaoError::MixedNotation)
+        );
+    }
+}
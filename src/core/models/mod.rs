@@ -1,7 +1,15 @@
+mod ba_gong;
+mod format;
 mod gua64;
 mod gua8;
+mod liu_yao_type;
+mod shu_zi;
 mod yao;
 
-pub use gua8::{Gua8, Gua8YaoIndex};
+pub use ba_gong::{BA_GONG_LIST, BaGong, GongShi};
+pub use format::{GuaFormat, ParseGuaError};
+pub use gua8::{FangWei, Gua8, Gua8YaoIndex};
 pub use gua64::{Gua64, Gua64YaoIndex};
+pub use liu_yao_type::LiuYaoType;
+pub use shu_zi::{ShuZi, ShuZiMode};
 pub use yao::Yao;
@@ -0,0 +1,169 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use strum::{Display, IntoStaticStr};
+
+use crate::core::utils::digits_rem;
+
+/// 取余时保留的余数，八和六的最小公倍数
+///
+/// 数字除以 24 的余数，既能求出除以八的余数（卦），也能求出除以六的余数（爻），
+/// 几个数相加后再取余也不受影响
+const YU_SHU_JI: u64 = 24;
+
+/// 数字起卦时由数字得到起卦数的方式
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Display, IntoStaticStr,
+)]
+pub enum ShuZiMode {
+    /// 整个数字除以八、除以六取余
+    #[default]
+    取余,
+    /// 各位数字相加后再取余
+    数字和,
+}
+
+impl ShuZiMode {
+    pub const ALL: [ShuZiMode; 2] = [ShuZiMode::取余, ShuZiMode::数字和];
+}
+
+/// 起卦用的数字，可以是任意长的数字串，如手机号、车牌号中的数字
+///
+/// 只保存数字，输入中的空格和 `-` 会被去掉
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ShuZi(String);
+
+impl ShuZi {
+    /// 数字串
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// 各位数字之和
+    pub fn digit_sum(&self) -> u64 {
+        self.0
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .map(|digit| digit as u64)
+            .sum()
+    }
+
+    /// 用于起卦的数
+    ///
+    /// 取余时只保留除以 24 的余数，数字和则为各位数字之和，两者都不会溢出
+    pub fn value(&self, mode: ShuZiMode) -> u64 {
+        match mode {
+            ShuZiMode::取余 => digits_rem(&self.0, YU_SHU_JI),
+            ShuZiMode::数字和 => self.digit_sum(),
+        }
+    }
+}
+
+impl fmt::Display for ShuZi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for ShuZi {
+    type Err = ParseShuZiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits: String = s
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .collect();
+
+        if digits.is_empty() {
+            return Err(ParseShuZiError::Empty);
+        }
+
+        if let Some(c) = digits.chars().find(|c| !c.is_ascii_digit()) {
+            return Err(ParseShuZiError::InvalidChar(c));
+        }
+
+        Ok(Self(digits))
+    }
+}
+
+impl From<u64> for ShuZi {
+    fn from(value: u64) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl From<ShuZi> for String {
+    fn from(value: ShuZi) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<String> for ShuZi {
+    type Error = ParseShuZiError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// 解析数字输入时的错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseShuZiError {
+    /// 输入为空
+    Empty,
+    /// 包含不是数字的字符
+    InvalidChar(char),
+}
+
+impl fmt::Display for ParseShuZiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseShuZiError::Empty => write!(f, "请输入数字"),
+            ParseShuZiError::InvalidChar(c) => write!(f, "无法识别“{c}”，只能输入数字"),
+        }
+    }
+}
+
+impl Error for ParseShuZiError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseShuZiError, ShuZi, ShuZiMode};
+
+    #[test]
+    /// 测试解析数字输入
+    fn test_parse() {
+        assert_eq!("128".parse::<ShuZi>().unwrap().as_str(), "128");
+        assert_eq!(
+            "138-0013 8000".parse::<ShuZi>().unwrap().as_str(),
+            "13800138000"
+        );
+
+        assert_eq!("".parse::<ShuZi>(), Err(ParseShuZiError::Empty));
+        assert_eq!(" - ".parse::<ShuZi>(), Err(ParseShuZiError::Empty));
+        assert_eq!(
+            "粤B12345".parse::<ShuZi>(),
+            Err(ParseShuZiError::InvalidChar('粤'))
+        );
+        assert_eq!(
+            "12.5".parse::<ShuZi>(),
+            Err(ParseShuZiError::InvalidChar('.'))
+        );
+    }
+
+    #[test]
+    /// 测试两种取数方式
+    fn test_value() {
+        let phone: ShuZi = "13800138000".parse().unwrap();
+
+        assert_eq!(phone.value(ShuZiMode::取余), 13800138000 % 24);
+        assert_eq!(phone.value(ShuZiMode::数字和), 24);
+
+        // 超出 u64 的长数字
+        let long: ShuZi = "9".repeat(100).parse().unwrap();
+        assert_eq!(long.value(ShuZiMode::数字和), 900);
+        // 10^100 - 1 除以 24 余 15
+        assert_eq!(long.value(ShuZiMode::取余), 15);
+    }
+}
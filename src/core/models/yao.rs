@@ -1,7 +1,8 @@
-use rand::random_bool;
+use serde::{Deserialize, Serialize};
 
 /// 爻，卦的基础单位，分为阴和阳
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Yao {
     pub status: bool,
 }
@@ -26,16 +27,15 @@ impl Yao {
         if self.status { "阳" } else { "阴" }
     }
 
+    /// 翻转后的爻
+    pub const fn reversed(&self) -> Self {
+        Yao {
+            status: !self.status,
+        }
+    }
+
     /// 翻转
     pub fn reverse(&mut self) {
         self.status = !self.status;
     }
-
-    /// 获取随机爻
-    pub fn random_yao() -> Self {
-        match random_bool(1.0 / 2.0) {
-            true => Yao::阳,
-            false => Yao::阴,
-        }
-    }
 }
@@ -0,0 +1,174 @@
+//! 京房纳甲
+//!
+//! 八卦各纳天干，六爻各配地支：
+//! * 乾内卦纳甲、外卦纳壬，坤内卦纳乙、外卦纳癸，其余六卦内外相同
+//! * 阳卦地支顺行，阴卦地支逆行，每爻隔一位，外卦接着内卦继续排
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{
+    gan_zhi::{DiZhi, TianGan, WuXing},
+    models::{Gua8, Gua64},
+};
+
+/// 一个爻的纳甲
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NaJiaYao {
+    /// 天干
+    pub gan: TianGan,
+    /// 地支
+    pub zhi: DiZhi,
+}
+
+impl NaJiaYao {
+    /// 五行，由地支决定
+    pub const fn wu_xing(&self) -> WuXing {
+        self.zhi.wu_xing()
+    }
+
+    /// 如 `甲子水`
+    pub fn display(&self) -> String {
+        format!("{}{}{}", self.gan, self.zhi, self.wu_xing())
+    }
+}
+
+/// 六个爻的纳甲，从初爻到上爻
+pub fn na_jia(gua: &Gua64) -> [NaJiaYao; 6] {
+    let xia = gua.xia();
+    let shang = gua.shang();
+
+    let nei_gan = nei_gan(xia);
+    let wai_gan = wai_gan(shang);
+
+    std::array::from_fn(|index| match index {
+        0..3 => NaJiaYao {
+            gan: nei_gan,
+            zhi: zhi(xia, index),
+        },
+        _ => NaJiaYao {
+            gan: wai_gan,
+            zhi: zhi(shang, index),
+        },
+    })
+}
+
+/// 内卦所纳天干
+fn nei_gan(gua: Gua8) -> TianGan {
+    match gua {
+        Gua8::乾 => TianGan::甲,
+        Gua8::坤 => TianGan::乙,
+        _ => wai_gan(gua),
+    }
+}
+
+/// 外卦所纳天干
+fn wai_gan(gua: Gua8) -> TianGan {
+    match gua {
+        Gua8::乾 => TianGan::壬,
+        Gua8::坤 => TianGan::癸,
+        Gua8::震 => TianGan::庚,
+        Gua8::巽 => TianGan::辛,
+        Gua8::坎 => TianGan::戊,
+        Gua8::离 => TianGan::己,
+        Gua8::艮 => TianGan::丙,
+        _ => TianGan::丁,
+    }
+}
+
+/// 八卦在某一爻位所纳的地支
+///
+/// * `position` - 爻位，初爻为 0，上爻为 5
+fn zhi(gua: Gua8, position: usize) -> DiZhi {
+    // 初爻的地支，以及是否顺行
+    let (start, is_shun) = match gua {
+        Gua8::乾 | Gua8::震 => (DiZhi::子, true),
+        Gua8::坎 => (DiZhi::寅, true),
+        Gua8::艮 => (DiZhi::辰, true),
+        Gua8::坤 => (DiZhi::未, false),
+        Gua8::巽 => (DiZhi::丑, false),
+        Gua8::离 => (DiZhi::卯, false),
+        _ => (DiZhi::巳, false),
+    };
+
+    let step = position * 2;
+
+    match is_shun {
+        true => DiZhi::from_index(start.index() + step),
+        false => DiZhi::from_index(start.index() + 12 - step),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{Gua8, Gua64};
+
+    use super::na_jia;
+
+    /// 纳甲对照表，从初爻到上爻
+    const REFERENCE: [(Gua64, &str); 14] = [
+        (Gua64::乾, "甲子 甲寅 甲辰 壬午 壬申 壬戌"),
+        (Gua64::坤, "乙未 乙巳 乙卯 癸丑 癸亥 癸酉"),
+        (Gua64::震, "庚子 庚寅 庚辰 庚午 庚申 庚戌"),
+        (Gua64::巽, "辛丑 辛亥 辛酉 辛未 辛巳 辛卯"),
+        (Gua64::坎, "戊寅 戊辰 戊午 戊申 戊戌 戊子"),
+        (Gua64::离, "己卯 己丑 己亥 己酉 己未 己巳"),
+        (Gua64::艮, "丙辰 丙午 丙申 丙戌 丙子 丙寅"),
+        (Gua64::兑, "丁巳 丁卯 丁丑 丁亥 丁酉 丁未"),
+        (Gua64::屯, "庚子 庚寅 庚辰 戊申 戊戌 戊子"),
+        (Gua64::泰, "甲子 甲寅 甲辰 癸丑 癸亥 癸酉"),
+        (Gua64::否, "乙未 乙巳 乙卯 壬午 壬申 壬戌"),
+        (Gua64::姤, "辛丑 辛亥 辛酉 壬午 壬申 壬戌"),
+        (Gua64::既济, "己卯 己丑 己亥 戊申 戊戌 戊子"),
+        (Gua64::大有, "甲子 甲寅 甲辰 己酉 己未 己巳"),
+    ];
+
+    #[test]
+    /// 测试纳甲对照表
+    fn test_na_jia() {
+        for (gua, expected) in REFERENCE {
+            let actual: Vec<String> = na_jia(&gua)
+                .iter()
+                .map(|yao| format!("{}{}", yao.gan, yao.zhi))
+                .collect();
+
+            assert_eq!(actual.join(" "), expected, "{}", gua.name());
+        }
+
+        assert_eq!(na_jia(&Gua64::屯)[0].display(), "庚子水");
+        assert_eq!(na_jia(&Gua64::屯)[2].display(), "庚辰土");
+    }
+
+    /// 八卦在内卦和外卦时所纳的干支，从下往上
+    const GUA8_REFERENCE: [(Gua8, &str, &str); 8] = [
+        (Gua8::乾, "甲子 甲寅 甲辰", "壬午 壬申 壬戌"),
+        (Gua8::坤, "乙未 乙巳 乙卯", "癸丑 癸亥 癸酉"),
+        (Gua8::震, "庚子 庚寅 庚辰", "庚午 庚申 庚戌"),
+        (Gua8::巽, "辛丑 辛亥 辛酉", "辛未 辛巳 辛卯"),
+        (Gua8::坎, "戊寅 戊辰 戊午", "戊申 戊戌 戊子"),
+        (Gua8::离, "己卯 己丑 己亥", "己酉 己未 己巳"),
+        (Gua8::艮, "丙辰 丙午 丙申", "丙戌 丙子 丙寅"),
+        (Gua8::兑, "丁巳 丁卯 丁丑", "丁亥 丁酉 丁未"),
+    ];
+
+    #[test]
+    /// 六十四卦逐一对照：内卦按下卦取内卦干支，外卦按上卦取外卦干支
+    fn test_na_jia_all() {
+        let reference = |gua: Gua8| {
+            GUA8_REFERENCE
+                .iter()
+                .find(|(gua8, _, _)| *gua8 == gua)
+                .unwrap()
+        };
+
+        for gua in Gua64::all() {
+            let actual: Vec<String> = na_jia(&gua)
+                .iter()
+                .map(|yao| format!("{}{}", yao.gan, yao.zhi))
+                .collect();
+
+            let expected = format!("{} {}", reference(gua.xia()).1, reference(gua.shang()).2);
+
+            assert_eq!(actual.join(" "), expected, "{}", gua.name());
+        }
+    }
+}
@@ -0,0 +1,391 @@
+//! 六爻排盘
+//!
+//! 在纳甲的基础上，按八宫五行定六亲，按日干起六神，按日辰定旬空
+
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use crate::core::{
+    ba_gua::GuaResult,
+    calendar::SiZhu,
+    gan_zhi::{DiZhi, GanZhi, TianGan, WuXing},
+    models::{BaGong, GongShi, Gua64, Gua64YaoIndex, Yao},
+    na_jia::{NaJiaYao, na_jia},
+    wang_shuai::YaoWangShuai,
+};
+
+/// 六亲
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Serialize, Deserialize)]
+pub enum LiuQin {
+    父母,
+    兄弟,
+    子孙,
+    妻财,
+    官鬼,
+}
+
+impl LiuQin {
+    /// 所有六亲
+    pub const ALL: [LiuQin; 5] = [
+        LiuQin::父母,
+        LiuQin::兄弟,
+        LiuQin::子孙,
+        LiuQin::妻财,
+        LiuQin::官鬼,
+    ];
+
+    /// 以宫的五行为我，比较爻的五行
+    ///
+    /// 生我者父母，同我者兄弟，我生者子孙，我克者妻财，克我者官鬼
+    pub fn new(gong: WuXing, yao: WuXing) -> Self {
+        if yao == gong {
+            LiuQin::兄弟
+        } else if yao.sheng() == gong {
+            LiuQin::父母
+        } else if gong.sheng() == yao {
+            LiuQin::子孙
+        } else if gong.ke() == yao {
+            LiuQin::妻财
+        } else {
+            LiuQin::官鬼
+        }
+    }
+}
+
+/// 六神
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Serialize, Deserialize)]
+pub enum LiuShen {
+    青龙,
+    朱雀,
+    勾陈,
+    螣蛇,
+    白虎,
+    玄武,
+}
+
+impl LiuShen {
+    /// 六神的顺序，从青龙到玄武
+    pub const ALL: [LiuShen; 6] = [
+        LiuShen::青龙,
+        LiuShen::朱雀,
+        LiuShen::勾陈,
+        LiuShen::螣蛇,
+        LiuShen::白虎,
+        LiuShen::玄武,
+    ];
+
+    /// 按日干起六神，从初爻往上排
+    ///
+    /// 甲乙起青龙，丙丁起朱雀，戊起勾陈，己起螣蛇，庚辛起白虎，壬癸起玄武
+    pub const fn from_day_gan(gan: TianGan) -> [LiuShen; 6] {
+        let start = match gan {
+            TianGan::甲 | TianGan::乙 => 0,
+            TianGan::丙 | TianGan::丁 => 1,
+            TianGan::戊 => 2,
+            TianGan::己 => 3,
+            TianGan::庚 | TianGan::辛 => 4,
+            TianGan::壬 | TianGan::癸 => 5,
+        };
+
+        let mut result = [LiuShen::青龙; 6];
+        let mut index = 0;
+
+        while index < 6 {
+            result[index] = Self::ALL[(start + index) % 6];
+            index += 1;
+        }
+
+        result
+    }
+}
+
+/// 排盘中的一个爻
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaiPanYao {
+    /// 爻位
+    pub index: Gua64YaoIndex,
+    /// 本卦的爻
+    pub yao: Yao,
+    /// 纳甲
+    pub na_jia: NaJiaYao,
+    /// 六亲
+    pub liu_qin: LiuQin,
+    /// 六神
+    pub liu_shen: LiuShen,
+    /// 是否为世爻
+    pub is_shi: bool,
+    /// 是否为应爻
+    pub is_ying: bool,
+    /// 是否为动爻
+    pub is_dong: bool,
+    /// 地支是否旬空
+    pub is_kong: bool,
+    /// 相对月建和日辰的旺衰
+    pub wang_shuai: YaoWangShuai,
+    /// 动爻变出的纳甲和六亲，六亲仍以本卦的宫为准
+    pub bian: Option<(NaJiaYao, LiuQin)>,
+}
+
+/// 伏神
+///
+/// 本卦六爻中缺少的六亲，到本宫卦中寻找，伏于本卦同一爻位之下，
+/// 该爻位上本卦的爻即为飞神
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuShen {
+    /// 爻位
+    pub index: Gua64YaoIndex,
+    /// 缺少的六亲
+    pub liu_qin: LiuQin,
+    /// 伏神在本宫卦中的纳甲
+    pub na_jia: NaJiaYao,
+    /// 飞神，即本卦同一爻位的纳甲
+    pub fei_shen: NaJiaYao,
+    /// 飞神的六亲
+    pub fei_liu_qin: LiuQin,
+}
+
+/// 六爻排盘
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaiPan {
+    /// 本卦
+    pub ben_gua: Gua64,
+    /// 本卦所在的宫
+    pub ba_gong: BaGong,
+    /// 月建，起卦时的月柱
+    pub yue_jian: GanZhi,
+    /// 日辰，起卦时的日柱
+    pub ri_chen: GanZhi,
+    /// 旬空
+    pub xun_kong: [DiZhi; 2],
+    /// 六个爻，从初爻到上爻
+    pub yao_list: Vec<PaiPanYao>,
+    /// 伏神，六亲齐全时为空
+    pub fu_shen: Vec<FuShen>,
+}
+
+impl PaiPan {
+    /// 根据算卦结果排盘，月建和日辰取 `GuaResult.date` 的四柱
+    pub fn new(result: &GuaResult) -> Self {
+        let ben_gua = &result.ben_gua;
+        let bian_gua = result.bian_gua();
+
+        let ba_gong = ben_gua.ba_gong();
+        let gong_wu_xing = ba_gong.gong.wu_xing();
+
+        let si_zhu = SiZhu::new(&result.date);
+        let yue_jian = si_zhu.yue;
+        let ri_chen = si_zhu.ri;
+        let xun_kong = ri_chen.xun_kong();
+        let liu_shen = LiuShen::from_day_gan(ri_chen.gan);
+
+        let ben_na_jia = na_jia(ben_gua);
+        let bian_na_jia = bian_gua.as_ref().map(na_jia);
+
+        let yao_list: Vec<PaiPanYao> = Gua64YaoIndex::ALL
+            .into_iter()
+            .enumerate()
+            .map(|(position, index)| {
+                let na_jia = ben_na_jia[position];
+                let is_dong = result.dong_yao.contains(&index);
+
+                let bian = match (is_dong, &bian_na_jia) {
+                    (true, Some(bian_na_jia)) => {
+                        let na_jia = bian_na_jia[position];
+
+                        Some((na_jia, LiuQin::new(gong_wu_xing, na_jia.wu_xing())))
+                    }
+                    _ => None,
+                };
+
+                PaiPanYao {
+                    index,
+                    yao: ben_gua.yao(index),
+                    na_jia,
+                    liu_qin: LiuQin::new(gong_wu_xing, na_jia.wu_xing()),
+                    liu_shen: liu_shen[position],
+                    is_shi: ben_gua.shi_yao() == index,
+                    is_ying: ben_gua.ying_yao() == index,
+                    is_dong,
+                    is_kong: xun_kong.contains(&na_jia.zhi),
+                    wang_shuai: YaoWangShuai::new(yue_jian.zhi, ri_chen.zhi, na_jia.zhi, is_dong),
+                    bian,
+                }
+            })
+            .collect();
+
+        let fu_shen = fu_shen(ba_gong, &yao_list);
+
+        Self {
+            ben_gua: ben_gua.clone(),
+            ba_gong,
+            yue_jian,
+            ri_chen,
+            xun_kong,
+            yao_list,
+            fu_shen,
+        }
+    }
+}
+
+/// 找出本卦缺少的六亲，以及它们在本宫卦中的位置
+///
+/// 本宫卦中同一六亲可能出现两次，此时都会列出
+fn fu_shen(ba_gong: BaGong, yao_list: &[PaiPanYao]) -> Vec<FuShen> {
+    let gong_wu_xing = ba_gong.gong.wu_xing();
+    let ben_gong_na_jia = na_jia(&BaGong::new(ba_gong.gong, GongShi::本宫).gua());
+
+    LiuQin::ALL
+        .into_iter()
+        .filter(|liu_qin| yao_list.iter().all(|yao| yao.liu_qin != *liu_qin))
+        .flat_map(|liu_qin| {
+            yao_list
+                .iter()
+                .zip(ben_gong_na_jia)
+                .filter(move |(_, na_jia)| LiuQin::new(gong_wu_xing, na_jia.wu_xing()) == liu_qin)
+                .map(move |(yao, na_jia)| FuShen {
+                    index: yao.index,
+                    liu_qin,
+                    na_jia,
+                    fei_shen: yao.na_jia,
+                    fei_liu_qin: yao.liu_qin,
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, TimeZone};
+
+    use crate::core::{
+        ba_gua::{GuaResult, QiGuaSource},
+        gan_zhi::{DiZhi, WuXing},
+        models::{Gua64, Gua64YaoIndex, LiuYaoType},
+    };
+
+    use super::{LiuQin, LiuShen, PaiPan};
+
+    #[test]
+    /// 测试六亲
+    fn test_liu_qin() {
+        assert_eq!(LiuQin::new(WuXing::水, WuXing::水), LiuQin::兄弟);
+        assert_eq!(LiuQin::new(WuXing::水, WuXing::金), LiuQin::父母);
+        assert_eq!(LiuQin::new(WuXing::水, WuXing::木), LiuQin::子孙);
+        assert_eq!(LiuQin::new(WuXing::水, WuXing::火), LiuQin::妻财);
+        assert_eq!(LiuQin::new(WuXing::水, WuXing::土), LiuQin::官鬼);
+    }
+
+    #[test]
+    /// 测试排盘：2000 年 1 月 1 日（戊午日）得屯卦，初爻动
+    fn test_pai_pan() {
+        let source = QiGuaSource::LiuYao([
+            LiuYaoType::动阳,
+            LiuYaoType::阴,
+            LiuYaoType::阴,
+            LiuYaoType::阴,
+            LiuYaoType::阳,
+            LiuYaoType::阴,
+        ]);
+
+        let mut result = GuaResult::new(Gua64::屯, vec![Gua64YaoIndex::First], source);
+        result.date = Local.with_ymd_and_hms(2000, 1, 1, 10, 0, 0).unwrap();
+
+        let pai_pan = PaiPan::new(&result);
+
+        assert_eq!(pai_pan.ben_gua, Gua64::屯);
+        assert_eq!(pai_pan.yue_jian.to_string(), "丙子");
+        assert_eq!(pai_pan.ri_chen.to_string(), "戊午");
+        assert_eq!(pai_pan.xun_kong, [DiZhi::子, DiZhi::丑]);
+
+        let liu_qin: Vec<String> = pai_pan
+            .yao_list
+            .iter()
+            .map(|yao| format!("{}{}", yao.liu_qin, yao.na_jia.display()))
+            .collect();
+        assert_eq!(
+            liu_qin,
+            [
+                "兄弟庚子水",
+                "子孙庚寅木",
+                "官鬼庚辰土",
+                "父母戊申金",
+                "官鬼戊戌土",
+                "兄弟戊子水"
+            ]
+        );
+
+        let liu_shen: Vec<LiuShen> = pai_pan.yao_list.iter().map(|yao| yao.liu_shen).collect();
+        assert_eq!(
+            liu_shen,
+            [
+                LiuShen::勾陈,
+                LiuShen::螣蛇,
+                LiuShen::白虎,
+                LiuShen::玄武,
+                LiuShen::青龙,
+                LiuShen::朱雀
+            ]
+        );
+
+        // 世二应五
+        assert!(pai_pan.yao_list[1].is_shi);
+        assert!(pai_pan.yao_list[4].is_ying);
+
+        // 子水旬空
+        assert!(pai_pan.yao_list[0].is_kong);
+        assert!(pai_pan.yao_list[5].is_kong);
+        assert!(!pai_pan.yao_list[1].is_kong);
+
+        // 子月午日，上爻子水静而旺，逢日冲为暗动
+        let badges: Vec<Vec<String>> = pai_pan
+            .yao_list
+            .iter()
+            .map(|yao| {
+                yao.wang_shuai
+                    .badges()
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .collect();
+        assert_eq!(badges[0], ["旺", "日冲"]);
+        assert_eq!(badges[1], ["相"]);
+        assert_eq!(badges[2], ["囚", "日生"]);
+        assert_eq!(badges[3], ["休", "日克"]);
+        assert_eq!(badges[5], ["旺", "日冲", "暗动"]);
+
+        // 初爻动，变卦为比，初爻乙未土，以坎宫论为官鬼
+        let (bian_na_jia, bian_liu_qin) = pai_pan.yao_list[0].bian.unwrap();
+        assert_eq!(bian_na_jia.display(), "乙未土");
+        assert_eq!(bian_liu_qin, LiuQin::官鬼);
+        assert_eq!(pai_pan.yao_list[1].bian, None);
+    }
+
+    #[test]
+    /// 测试伏神和飞神
+    fn test_fu_shen() {
+        let source = QiGuaSource::LiuYao([LiuYaoType::阳; 6]);
+
+        // 乾为本宫卦，六亲齐全
+        let result = GuaResult::new(Gua64::乾, vec![], source.clone());
+        assert!(PaiPan::new(&result).fu_shen.is_empty());
+
+        // 屯属坎宫（水），缺妻财，坎为水三爻戊午火为伏神，屯三爻庚辰土为飞神
+        let result = GuaResult::new(Gua64::屯, vec![], source.clone());
+        let fu_shen = PaiPan::new(&result).fu_shen;
+        assert_eq!(fu_shen.len(), 1);
+        assert_eq!(fu_shen[0].index, Gua64YaoIndex::Third);
+        assert_eq!(fu_shen[0].liu_qin, LiuQin::妻财);
+        assert_eq!(fu_shen[0].na_jia.display(), "戊午火");
+        assert_eq!(fu_shen[0].fei_shen.display(), "庚辰土");
+        assert_eq!(fu_shen[0].fei_liu_qin, LiuQin::官鬼);
+
+        // 姤属乾宫（金），缺妻财，乾为天二爻甲寅木伏于姤二爻辛亥水之下
+        let result = GuaResult::new(Gua64::姤, vec![], source);
+        let fu_shen = PaiPan::new(&result).fu_shen;
+        assert_eq!(fu_shen.len(), 1);
+        assert_eq!(fu_shen[0].index, Gua64YaoIndex::Second);
+        assert_eq!(fu_shen[0].na_jia.display(), "甲寅木");
+        assert_eq!(fu_shen[0].fei_shen.display(), "辛亥水");
+    }
+}
@@ -9,29 +9,53 @@
 /// * `number`: 被除数
 /// * `base`: 基数
 /// ```
-pub fn ichang_mod(number: u16, base: u16) -> u8 {
-    let a = number % base;
+pub fn ichang_mod(number: impl Into<u64>, base: u8) -> u8 {
+    let a = number.into() % base as u64;
 
     match a {
-        0 => base as u8,
+        0 => base,
         _ => a as u8,
     }
 }
 
+/// 十进制数字串除以 `base` 的余数，不限数字的长度
+///
+/// 逐位计算，不会溢出。数字串中不是数字的字符会被忽略。
+pub fn digits_rem(digits: &str, base: u64) -> u64 {
+    digits
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .fold(0, |rem, digit| (rem * 10 + digit as u64) % base)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::ichang_mod;
+    use super::{digits_rem, ichang_mod};
 
     #[test]
     /// 测试 ichang_mod 函数
     fn test_ichang_mod() {
-        assert_eq!(ichang_mod(0, 6), 6);
-        assert_eq!(ichang_mod(1, 6), 1);
-        assert_eq!(ichang_mod(2, 6), 2);
-        assert_eq!(ichang_mod(3, 6), 3);
-        assert_eq!(ichang_mod(4, 6), 4);
-        assert_eq!(ichang_mod(5, 6), 5);
-        assert_eq!(ichang_mod(6, 6), 6);
-        assert_eq!(ichang_mod(7, 6), 1);
+        assert_eq!(ichang_mod(0u16, 6), 6);
+        assert_eq!(ichang_mod(1u16, 6), 1);
+        assert_eq!(ichang_mod(2u16, 6), 2);
+        assert_eq!(ichang_mod(3u16, 6), 3);
+        assert_eq!(ichang_mod(4u16, 6), 4);
+        assert_eq!(ichang_mod(5u16, 6), 5);
+        assert_eq!(ichang_mod(6u16, 6), 6);
+        assert_eq!(ichang_mod(7u16, 6), 1);
+        assert_eq!(ichang_mod(u64::MAX, 8), 7);
+    }
+
+    #[test]
+    /// 测试 digits_rem 函数
+    fn test_digits_rem() {
+        assert_eq!(digits_rem("", 8), 0);
+        assert_eq!(digits_rem("128", 8), 0);
+        assert_eq!(digits_rem("161", 6), 5);
+        assert_eq!(digits_rem("18446744073709551615", 24), u64::MAX % 24);
+        // 远超 u64 的数字：10^40 + 7
+        let big = format!("1{}7", "0".repeat(39));
+        assert_eq!(digits_rem(&big, 8), 7);
+        assert_eq!(digits_rem(&big, 6), (4 + 7) % 6);
     }
 }
@@ -1,3 +1,3 @@
 mod ichang;
 
-pub use ichang::ichang_mod;
+pub use ichang::{digits_rem, ichang_mod};
@@ -0,0 +1,223 @@
+//! 旺衰分析
+//!
+//! 以月建定爻的旺相休囚死，以日辰看生克冲合，并判断月破、暗动
+
+use std::fmt;
+
+use strum::Display;
+
+use crate::core::gan_zhi::{DiZhi, WuXing};
+
+/// 爻在月令中的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum WangShuai {
+    /// 与月令同五行
+    旺,
+    /// 月令生爻
+    相,
+    /// 爻生月令
+    休,
+    /// 爻克月令
+    囚,
+    /// 月令克爻
+    死,
+}
+
+impl WangShuai {
+    /// * `yue` - 月令的五行
+    /// * `yao` - 爻的五行
+    pub fn new(yue: WuXing, yao: WuXing) -> Self {
+        if yue == yao {
+            WangShuai::旺
+        } else if yue.sheng() == yao {
+            WangShuai::相
+        } else if yao.sheng() == yue {
+            WangShuai::休
+        } else if yao.ke() == yue {
+            WangShuai::囚
+        } else {
+            WangShuai::死
+        }
+    }
+
+    /// 旺相为有气
+    pub const fn is_strong(&self) -> bool {
+        matches!(self, WangShuai::旺 | WangShuai::相)
+    }
+}
+
+/// 日辰对爻的作用
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum RiChenZuoYong {
+    /// 日辰五行生爻
+    日生,
+    /// 日辰五行克爻
+    日克,
+    /// 日辰与爻相冲
+    日冲,
+    /// 日辰与爻六合
+    日合,
+}
+
+/// 一个爻的旺衰
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YaoWangShuai {
+    /// 旺相休囚死
+    pub wang_shuai: WangShuai,
+    /// 日辰的作用，可能同时有多个
+    pub ri_chen: Vec<RiChenZuoYong>,
+    /// 月建冲爻
+    pub is_yue_po: bool,
+    /// 静爻有气，被日辰所冲
+    pub is_an_dong: bool,
+}
+
+impl YaoWangShuai {
+    /// * `yue_jian` - 月建的地支
+    /// * `ri_chen` - 日辰的地支
+    /// * `zhi` - 爻所纳的地支
+    /// * `is_dong` - 是否为动爻
+    pub fn new(yue_jian: DiZhi, ri_chen: DiZhi, zhi: DiZhi, is_dong: bool) -> Self {
+        let wang_shuai = WangShuai::new(yue_jian.wu_xing(), zhi.wu_xing());
+
+        let ri_chen_list = [
+            (
+                ri_chen.wu_xing().sheng() == zhi.wu_xing(),
+                RiChenZuoYong::日生,
+            ),
+            (ri_chen.wu_xing().ke() == zhi.wu_xing(), RiChenZuoYong::日克),
+            (ri_chen.chong() == zhi, RiChenZuoYong::日冲),
+            (ri_chen.he() == zhi, RiChenZuoYong::日合),
+        ]
+        .into_iter()
+        .filter_map(|(matched, zuo_yong)| matched.then_some(zuo_yong))
+        .collect();
+
+        Self {
+            wang_shuai,
+            ri_chen: ri_chen_list,
+            is_yue_po: yue_jian.chong() == zhi,
+            is_an_dong: !is_dong && wang_shuai.is_strong() && ri_chen.chong() == zhi,
+        }
+    }
+
+    /// 用于展示的标记，如 `旺`、`日冲`、`暗动`
+    pub fn badges(&self) -> Vec<WangShuaiBadge> {
+        let mut badges = vec![WangShuaiBadge::旺衰(self.wang_shuai)];
+
+        badges.extend(self.ri_chen.iter().copied().map(WangShuaiBadge::日辰));
+
+        if self.is_yue_po {
+            badges.push(WangShuaiBadge::月破);
+        }
+
+        if self.is_an_dong {
+            badges.push(WangShuaiBadge::暗动);
+        }
+
+        badges
+    }
+}
+
+/// 旺衰的展示标记
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WangShuaiBadge {
+    /// 旺相休囚死
+    旺衰(WangShuai),
+    /// 日辰的作用
+    日辰(RiChenZuoYong),
+    /// 月建冲爻
+    月破,
+    /// 静爻有气，被日辰所冲
+    暗动,
+}
+
+impl WangShuaiBadge {
+    /// 月破、暗动需要醒目提示
+    pub const fn is_warning(&self) -> bool {
+        matches!(self, WangShuaiBadge::月破 | WangShuaiBadge::暗动)
+    }
+}
+
+impl fmt::Display for WangShuaiBadge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WangShuaiBadge::旺衰(wang_shuai) => write!(f, "{wang_shuai}"),
+            WangShuaiBadge::日辰(zuo_yong) => write!(f, "{zuo_yong}"),
+            WangShuaiBadge::月破 => write!(f, "月破"),
+            WangShuaiBadge::暗动 => write!(f, "暗动"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::gan_zhi::{DiZhi, WuXing};
+
+    use super::{RiChenZuoYong, WangShuai, WangShuaiBadge, YaoWangShuai};
+
+    #[test]
+    /// 测试旺相休囚死
+    fn test_wang_shuai() {
+        assert_eq!(WangShuai::new(WuXing::水, WuXing::水), WangShuai::旺);
+        assert_eq!(WangShuai::new(WuXing::水, WuXing::木), WangShuai::相);
+        assert_eq!(WangShuai::new(WuXing::水, WuXing::金), WangShuai::休);
+        assert_eq!(WangShuai::new(WuXing::水, WuXing::土), WangShuai::囚);
+        assert_eq!(WangShuai::new(WuXing::水, WuXing::火), WangShuai::死);
+    }
+
+    #[test]
+    /// 测试日辰作用、月破和暗动
+    fn test_yao_wang_shuai() {
+        // 子月午日，子水静爻旺而逢日冲，为暗动
+        let yao = YaoWangShuai::new(DiZhi::子, DiZhi::午, DiZhi::子, false);
+        assert_eq!(yao.wang_shuai, WangShuai::旺);
+        assert_eq!(yao.ri_chen, [RiChenZuoYong::日冲]);
+        assert!(yao.is_an_dong);
+        assert!(!yao.is_yue_po);
+        assert_eq!(
+            yao.badges(),
+            [
+                WangShuaiBadge::旺衰(WangShuai::旺),
+                WangShuaiBadge::日辰(RiChenZuoYong::日冲),
+                WangShuaiBadge::暗动
+            ]
+        );
+        assert!(yao.badges().iter().any(WangShuaiBadge::is_warning));
+        assert_eq!(yao.badges()[2].to_string(), "暗动");
+
+        // 动爻逢冲不算暗动
+        let yao = YaoWangShuai::new(DiZhi::子, DiZhi::午, DiZhi::子, true);
+        assert!(!yao.is_an_dong);
+
+        // 子月午日，午火月破，午火与日辰同支
+        let yao = YaoWangShuai::new(DiZhi::子, DiZhi::午, DiZhi::午, false);
+        assert_eq!(yao.wang_shuai, WangShuai::死);
+        assert!(yao.is_yue_po);
+        assert!(yao.ri_chen.is_empty());
+        assert_eq!(
+            yao.badges(),
+            [WangShuaiBadge::旺衰(WangShuai::死), WangShuaiBadge::月破]
+        );
+
+        // 午日生辰土，合未土
+        let yao = YaoWangShuai::new(DiZhi::子, DiZhi::午, DiZhi::辰, false);
+        assert_eq!(yao.wang_shuai, WangShuai::囚);
+        assert_eq!(yao.ri_chen, [RiChenZuoYong::日生]);
+
+        let yao = YaoWangShuai::new(DiZhi::子, DiZhi::午, DiZhi::未, false);
+        assert_eq!(yao.ri_chen, [RiChenZuoYong::日生, RiChenZuoYong::日合]);
+
+        // 午日克申金
+        let yao = YaoWangShuai::new(DiZhi::子, DiZhi::午, DiZhi::申, false);
+        assert_eq!(yao.wang_shuai, WangShuai::休);
+        assert_eq!(yao.ri_chen, [RiChenZuoYong::日克]);
+        assert!(!yao.badges().iter().any(WangShuaiBadge::is_warning));
+
+        // 静爻无气逢日冲，不算暗动
+        let yao = YaoWangShuai::new(DiZhi::午, DiZhi::寅, DiZhi::申, false);
+        assert_eq!(yao.wang_shuai, WangShuai::死);
+        assert!(yao.ri_chen.contains(&RiChenZuoYong::日冲));
+        assert!(!yao.is_an_dong);
+    }
+}
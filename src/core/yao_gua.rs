@@ -0,0 +1,92 @@
+//! 摇卦：三枚铜钱掷六次，从初爻开始
+//!
+//! 字为阴，记二；背为阳，记三。三枚相加：
+//!
+//! * 六：三字，为老阴（交），动爻
+//! * 七：两字一背，为少阳（单）
+//! * 八：一字两背，为少阴（拆）
+//! * 九：三背，为老阳（重），动爻
+
+use rand::random_bool;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use crate::core::models::LiuYaoType;
+
+/// 铜钱的一面，字为正面，背为反面
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Serialize, Deserialize)]
+pub enum CoinSide {
+    字,
+    背,
+}
+
+impl CoinSide {
+    /// 随机掷出一面
+    pub fn random() -> Self {
+        match random_bool(1.0 / 2.0) {
+            true => CoinSide::字,
+            false => CoinSide::背,
+        }
+    }
+
+    /// 字记二，背记三
+    pub const fn number(&self) -> u8 {
+        match self {
+            CoinSide::字 => 2,
+            CoinSide::背 => 3,
+        }
+    }
+}
+
+/// 一次投掷三枚铜钱的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoinToss(pub [CoinSide; 3]);
+
+impl CoinToss {
+    /// 随机投掷一次
+    pub fn random() -> Self {
+        Self([CoinSide::random(), CoinSide::random(), CoinSide::random()])
+    }
+
+    /// 三枚铜钱相加，得六、七、八、九
+    pub fn number(&self) -> u8 {
+        self.0.iter().map(|side| side.number()).sum()
+    }
+
+    /// 六为老阴，七为少阳，八为少阴，九为老阳
+    pub fn liu_yao_type(&self) -> LiuYaoType {
+        LiuYaoType::from_number(self.number()).expect("三枚铜钱相加只会是六、七、八、九")
+    }
+
+    /// 如 `字字背`
+    pub fn display(&self) -> String {
+        self.0.iter().map(|side| side.to_string()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::LiuYaoType;
+
+    use super::{CoinSide, CoinToss};
+
+    #[test]
+    /// 测试铜钱的组合
+    fn test_coin_toss() {
+        let toss = CoinToss([CoinSide::字, CoinSide::字, CoinSide::字]);
+        assert_eq!(toss.number(), 6);
+        assert_eq!(toss.liu_yao_type(), LiuYaoType::动阴);
+
+        let toss = CoinToss([CoinSide::字, CoinSide::背, CoinSide::字]);
+        assert_eq!(toss.number(), 7);
+        assert_eq!(toss.liu_yao_type(), LiuYaoType::阳);
+        assert_eq!(toss.display(), "字背字");
+
+        let toss = CoinToss([CoinSide::背, CoinSide::背, CoinSide::字]);
+        assert_eq!(toss.liu_yao_type(), LiuYaoType::阴);
+
+        let toss = CoinToss([CoinSide::背; 3]);
+        assert_eq!(toss.number(), 9);
+        assert_eq!(toss.liu_yao_type(), LiuYaoType::动阳);
+    }
+}
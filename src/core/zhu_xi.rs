@@ -0,0 +1,231 @@
+//! 朱熹《易学启蒙·考变占》的断卦规则
+//!
+//! 根据动爻的数量，决定以哪些卦辞、爻辞为占
+
+use crate::core::{
+    ba_gua::GuaResult,
+    basic::Gua64Info,
+    models::{Gua64, Gua64YaoIndex},
+};
+
+/// 用九、用六在 `yao_ci` 中的位置
+const YONG_INDEX: usize = 6;
+
+/// 用于断卦的一条文辞
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZhanCi {
+    /// 出处，如 `本卦乾 初爻`
+    pub title: String,
+    /// 文辞内容
+    pub content: String,
+    /// 是否为主
+    pub is_main: bool,
+}
+
+/// 断卦结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZhuXiReading {
+    /// 适用的规则
+    pub rule: &'static str,
+    /// 按主次排列的文辞
+    pub ci_list: Vec<ZhanCi>,
+}
+
+/// 根据算卦结果得出应当参考的文辞
+///
+/// * `result` - 算卦结果
+/// * `info_list` - 64卦信息
+pub fn zhan_ci(result: &GuaResult, info_list: &[Gua64Info]) -> ZhuXiReading {
+    zhan_ci_by_dong_yao(&result.ben_gua, &result.dong_yao, info_list)
+}
+
+/// 根据本卦和动爻得出应当参考的文辞
+///
+/// * `dong_yao` - 动爻，需要从下往上排列
+fn zhan_ci_by_dong_yao(
+    ben_gua: &Gua64,
+    dong_yao: &[Gua64YaoIndex],
+    info_list: &[Gua64Info],
+) -> ZhuXiReading {
+    let zhi_gua = ben_gua.changed(dong_yao);
+
+    let ben_info = Gua64Info::find(info_list, ben_gua);
+    let zhi_info = Gua64Info::find(info_list, &zhi_gua);
+
+    // 不变的爻，从下往上
+    let jing_yao: Vec<Gua64YaoIndex> = Gua64YaoIndex::ALL
+        .into_iter()
+        .filter(|index| !dong_yao.contains(index))
+        .collect();
+
+    let ben_name = format!("本卦{}", ben_gua.name());
+    let zhi_name = format!("之卦{}", zhi_gua.name());
+
+    match dong_yao.len() {
+        0 => ZhuXiReading {
+            rule: "六爻皆不变，占本卦卦辞",
+            ci_list: vec![gua_ci(ben_info, &ben_name, true)],
+        },
+        1 => ZhuXiReading {
+            rule: "一爻变，以本卦变爻之辞占",
+            ci_list: vec![yao_ci(ben_info, &ben_name, dong_yao[0], true)],
+        },
+        2 => ZhuXiReading {
+            rule: "二爻变，以本卦二变爻之辞占，以上爻为主",
+            ci_list: vec![
+                yao_ci(ben_info, &ben_name, dong_yao[1], true),
+                yao_ci(ben_info, &ben_name, dong_yao[0], false),
+            ],
+        },
+        3 => ZhuXiReading {
+            rule: "三爻变，占本卦及之卦卦辞，以本卦为贞，之卦为悔",
+            ci_list: vec![
+                gua_ci(ben_info, &ben_name, true),
+                gua_ci(zhi_info, &zhi_name, false),
+            ],
+        },
+        4 => ZhuXiReading {
+            rule: "四爻变，以之卦二不变爻之辞占，以下爻为主",
+            ci_list: vec![
+                yao_ci(zhi_info, &zhi_name, jing_yao[0], true),
+                yao_ci(zhi_info, &zhi_name, jing_yao[1], false),
+            ],
+        },
+        5 => ZhuXiReading {
+            rule: "五爻变，以之卦不变爻之辞占",
+            ci_list: vec![yao_ci(zhi_info, &zhi_name, jing_yao[0], true)],
+        },
+        _ if *ben_gua == Gua64::乾 || *ben_gua == Gua64::坤 => ZhuXiReading {
+            rule: "六爻皆变，乾坤占二用",
+            ci_list: vec![yong_ci(ben_info, &ben_name)],
+        },
+        _ => ZhuXiReading {
+            rule: "六爻皆变，占之卦卦辞",
+            ci_list: vec![gua_ci(zhi_info, &zhi_name, true)],
+        },
+    }
+}
+
+/// 卦辞
+fn gua_ci(info: Option<&Gua64Info>, gua_name: &str, is_main: bool) -> ZhanCi {
+    ZhanCi {
+        title: format!("{gua_name} 卦辞"),
+        content: info.map(|info| info.gua_ci.clone()).unwrap_or_default(),
+        is_main,
+    }
+}
+
+/// 爻辞
+fn yao_ci(info: Option<&Gua64Info>, gua_name: &str, index: Gua64YaoIndex, is_main: bool) -> ZhanCi {
+    let position = index as usize - 1;
+
+    ZhanCi {
+        title: format!("{gua_name} {}", index.name()),
+        content: info
+            .and_then(|info| info.yao_ci.get(position).cloned())
+            .unwrap_or_default(),
+        is_main,
+    }
+}
+
+/// 用九、用六
+fn yong_ci(info: Option<&Gua64Info>, gua_name: &str) -> ZhanCi {
+    ZhanCi {
+        title: format!("{gua_name} 用辞"),
+        content: info
+            .and_then(|info| info.yao_ci.get(YONG_INDEX).cloned())
+            .unwrap_or_default(),
+        is_main: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        assets::init_gua64_info,
+        core::models::{Gua64, Gua64YaoIndex},
+    };
+
+    use super::zhan_ci_by_dong_yao;
+
+    #[test]
+    /// 测试不同动爻数量的断卦规则
+    fn test_zhan_ci() {
+        let info_list = init_gua64_info().unwrap();
+
+        // 不变，本卦卦辞
+        let r0 = zhan_ci_by_dong_yao(&Gua64::屯, &[], &info_list);
+        assert_eq!(r0.ci_list.len(), 1);
+        assert!(r0.ci_list[0].content.starts_with("元亨，利贞"));
+
+        // 一爻变，本卦变爻
+        let r1 = zhan_ci_by_dong_yao(&Gua64::乾, &[Gua64YaoIndex::First], &info_list);
+        assert_eq!(r1.ci_list[0].content, "初九：潜龙，勿用。");
+
+        // 二爻变，上爻为主
+        let r2 = zhan_ci_by_dong_yao(
+            &Gua64::乾,
+            &[Gua64YaoIndex::Second, Gua64YaoIndex::Fifth],
+            &info_list,
+        );
+        assert_eq!(r2.ci_list[0].content, "九五：飞龙在天，利见大人。");
+        assert!(r2.ci_list[0].is_main);
+        assert_eq!(r2.ci_list[1].content, "九二：见龙在田，利见大人。");
+        assert!(!r2.ci_list[1].is_main);
+
+        // 三爻变，本卦和之卦卦辞
+        let r3 = zhan_ci_by_dong_yao(
+            &Gua64::乾,
+            &[
+                Gua64YaoIndex::First,
+                Gua64YaoIndex::Second,
+                Gua64YaoIndex::Third,
+            ],
+            &info_list,
+        );
+        assert!(r3.ci_list[0].content.starts_with("乾："));
+        assert!(r3.ci_list[1].content.starts_with("否之匪人"));
+
+        // 四爻变，之卦不变爻，下爻为主
+        // 乾变初、二、三、四爻，之卦为观，不变的是五爻和上爻
+        let r4 = zhan_ci_by_dong_yao(
+            &Gua64::乾,
+            &[
+                Gua64YaoIndex::First,
+                Gua64YaoIndex::Second,
+                Gua64YaoIndex::Third,
+                Gua64YaoIndex::Fourth,
+            ],
+            &info_list,
+        );
+        assert!(r4.ci_list[0].title.starts_with("之卦观 五爻"));
+        assert!(r4.ci_list[0].content.starts_with("九五"));
+        assert!(r4.ci_list[1].content.starts_with("上九"));
+
+        // 五爻变，之卦不变爻
+        let r5 = zhan_ci_by_dong_yao(
+            &Gua64::坤,
+            &[
+                Gua64YaoIndex::First,
+                Gua64YaoIndex::Second,
+                Gua64YaoIndex::Fourth,
+                Gua64YaoIndex::Fifth,
+                Gua64YaoIndex::Sixth,
+            ],
+            &info_list,
+        );
+        assert_eq!(r5.ci_list.len(), 1);
+        assert!(r5.ci_list[0].title.starts_with("之卦履 三爻"));
+
+        // 六爻变，乾坤用九用六
+        let r6 = zhan_ci_by_dong_yao(&Gua64::乾, &Gua64YaoIndex::ALL, &info_list);
+        assert_eq!(r6.ci_list[0].content, "用九：见群龙无首，吉。");
+
+        let r6 = zhan_ci_by_dong_yao(&Gua64::坤, &Gua64YaoIndex::ALL, &info_list);
+        assert_eq!(r6.ci_list[0].content, "用六：利永贞。");
+
+        // 六爻变，其他卦占之卦卦辞
+        let r6 = zhan_ci_by_dong_yao(&Gua64::屯, &Gua64YaoIndex::ALL, &info_list);
+        assert!(r6.ci_list[0].content.starts_with("鼎："));
+    }
+}
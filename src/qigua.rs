@@ -1,4 +1,6 @@
 pub mod core;
+pub mod da_yan;
 pub mod liu_yao;
 pub mod time;
 pub mod two_number;
+pub mod yao_gua;
@@ -0,0 +1,104 @@
+use gpui::{
+    App, AppContext, Context, Entity, IntoElement, ParentElement, Render, SharedString, Styled,
+    Window, div,
+};
+use gpui_component::{
+    ActiveTheme, StyledExt,
+    button::{Button, ButtonVariants},
+};
+
+use crate::{
+    core::{
+        ba_gua::{GuaResult, QiGuaSource},
+        da_yan::DaYan,
+    },
+    qigua::core::QiGuaCore,
+    state::global::GlobalState,
+};
+
+const NAME: &str = "大衍筮法";
+
+/// 大衍筮法起卦
+pub struct DaYanQiGua {
+    content: Entity<DaYanContent>,
+}
+
+impl DaYanQiGua {
+    pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
+        cx.new(|cx| Self::new(window, cx))
+    }
+
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let content = DaYanContent::view(window, cx);
+
+        Self { content }
+    }
+}
+
+impl Render for DaYanQiGua {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        div().p_2().child(self.content.clone())
+    }
+}
+
+/// 模拟揲蓍来计算卦象
+pub struct DaYanContent;
+
+impl DaYanContent {
+    pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
+        cx.new(|cx| Self::new(window, cx))
+    }
+
+    fn new(_: &mut Window, _: &mut Context<Self>) -> Self {
+        Self {}
+    }
+}
+
+impl Render for DaYanContent {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entity = cx.entity();
+
+        div()
+            .w_80()
+            .p_2()
+            .v_flex()
+            .gap_2()
+            .child(NAME)
+            .child(
+                div()
+                    .text_color(cx.theme().muted_foreground)
+                    .child("取蓍草四十九根，经分二、挂一、揲四、归奇，三变成一爻，十八变成一卦"),
+            )
+            .child(
+                Button::new("calc")
+                    .label("开始揲蓍")
+                    .primary()
+                    .on_click(move |_, _, cx| {
+                        cx.update_entity(
+                            &entity,
+                            |input: &mut DaYanContent, context: &mut Context<DaYanContent>| {
+                                input.calc_gua(context)
+                            },
+                        );
+                    }),
+            )
+    }
+}
+
+impl QiGuaCore for DaYanContent {
+    fn calc_gua(&mut self, cx: &mut Context<Self>) {
+        let da_yan = DaYan::random();
+
+        let liu_yao_list = da_yan.liu_yao_list();
+
+        let ba_gua_result = GuaResult::from_liu_yao(liu_yao_list, QiGuaSource::DaYan(da_yan));
+
+        GlobalState::save_result(cx, ba_gua_result);
+
+        cx.notify();
+    }
+
+    fn name() -> SharedString {
+        NAME.into()
+    }
+}
@@ -1,25 +1,26 @@
 use crate::{
     core::{
-        ba_gua::GuaResult,
-        models::{Gua8, Gua64, Gua64YaoIndex, Yao},
+        ba_gua::{GuaResult, QiGuaSource},
+        models::LiuYaoType,
     },
     qigua::core::QiGuaCore,
     state::global::GlobalState,
+    ui::gua_xiang::GuaXiang,
 };
 use gpui::{
-    App, AppContext, Context, Entity, IntoElement, ParentElement, Render, SharedString, Styled,
-    Subscription, Window, div,
+    App, AppContext, Context, Div, Entity, IntoElement, ParentElement, Render, SharedString,
+    Styled, Subscription, Window, div, prelude::FluentBuilder, px,
 };
 use gpui_component::{
-    Disableable, Icon, StyledExt,
+    ActiveTheme, Disableable, Icon, StyledExt,
     button::{Button, ButtonVariants},
     h_flex,
+    input::{Input, InputState},
     label::Label,
     select::{Select, SelectEvent, SelectState},
     v_flex,
 };
 use std::str::FromStr;
-use strum::{Display, EnumString, IntoStaticStr};
 
 const NAME: &str = "六爻";
 
@@ -60,6 +61,10 @@ pub struct LiuYaoContent {
     second_yao: Entity<SingalYaoSelect>,
     /// 初爻（一爻）
     first_yao: Entity<SingalYaoSelect>,
+    /// 用数字输入六个爻
+    number_input: Entity<InputState>,
+    /// 数字输入有误时的提示
+    number_error: Option<String>,
 }
 
 impl LiuYaoContent {
@@ -75,9 +80,63 @@ impl LiuYaoContent {
             third_yao: SingalYaoSelect::view(window, cx, SharedString::new_static("三爻")),
             second_yao: SingalYaoSelect::view(window, cx, SharedString::new_static("二爻")),
             first_yao: SingalYaoSelect::view(window, cx, SharedString::new_static("初爻")),
+            number_input: cx
+                .new(|cx| InputState::new(window, cx).placeholder("如 789667，或字的数量 210332")),
+            number_error: None,
         }
     }
 
+    /// 从初爻到上爻的六个选择框
+    fn yao_select_list(&self) -> [&Entity<SingalYaoSelect>; 6] {
+        [
+            &self.first_yao,
+            &self.second_yao,
+            &self.third_yao,
+            &self.fourth_yao,
+            &self.fifth_yao,
+            &self.sixth_yao,
+        ]
+    }
+
+    /// 解析输入的数字，并填入六个爻
+    fn fill_from_number(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let input = self.number_input.read(cx).value().to_string();
+
+        match LiuYaoType::parse_list(&input) {
+            Ok(yao_list) => {
+                self.number_error = None;
+
+                for (select, yao) in self.yao_select_list().into_iter().zip(yao_list) {
+                    select.update(cx, |select, cx| select.set_yao(yao, window, cx));
+                }
+            }
+            Err(err) => self.number_error = Some(err.to_string()),
+        }
+
+        cx.notify();
+    }
+
+    /// 数字输入
+    ///
+    /// 初爻在前，六为老阴，七为少阳，八为少阴，九为老阳；也可以输入每次投掷中字的数量
+    fn number_content(&self, cx: &mut Context<Self>) -> Div {
+        v_flex()
+            .gap_1()
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(Input::new(&self.number_input))
+                    .child(Button::new("fill-number").outline().label("填入").on_click(
+                        cx.listener(|this, _, window, cx| {
+                            this.fill_from_number(window, cx);
+                        }),
+                    )),
+            )
+            .when_some(self.number_error.clone(), |this, error| {
+                this.child(div().text_color(cx.theme().danger).child(error))
+            })
+    }
+
     /// 选择爻象
     fn select_content(&mut self) -> impl IntoElement {
         v_flex().gap_2().children([
@@ -90,6 +149,52 @@ impl LiuYaoContent {
         ])
     }
 
+    /// 已选择的六个爻，从初爻到上爻，有爻未选择时为 `None`
+    fn liu_yao_list(&self, cx: &mut Context<Self>) -> Option<[LiuYaoType; 6]> {
+        let yao_list = [
+            self.first_yao.read(cx).yao,
+            self.second_yao.read(cx).yao,
+            self.third_yao.read(cx).yao,
+            self.fourth_yao.read(cx).yao,
+            self.fifth_yao.read(cx).yao,
+            self.sixth_yao.read(cx).yao,
+        ];
+
+        match yao_list {
+            [
+                Some(first),
+                Some(second),
+                Some(third),
+                Some(fourth),
+                Some(fifth),
+                Some(sixth),
+            ] => Some([first, second, third, fourth, fifth, sixth]),
+            _ => None,
+        }
+    }
+
+    /// 已选择的卦象预览
+    ///
+    /// 六个爻都选择后才会显示
+    fn preview_content(&self, cx: &mut Context<Self>) -> Div {
+        match self.liu_yao_list(cx) {
+            Some(liu_yao_list) => {
+                let result =
+                    GuaResult::from_liu_yao(liu_yao_list, QiGuaSource::LiuYao(liu_yao_list));
+
+                h_flex()
+                    .gap_2()
+                    .child(
+                        GuaXiang::gua64(&result.ben_gua)
+                            .highlight(&result.dong_yao)
+                            .size(px(48.)),
+                    )
+                    .child(result.ben_gua.name())
+            }
+            None => h_flex(),
+        }
+    }
+
     /// 是否可以执行计算
     fn is_can_calc(&self, cx: &mut Context<Self>) -> bool {
         self.sixth_yao.read(cx).is_selected
@@ -110,7 +215,9 @@ impl Render for LiuYaoContent {
             .v_flex()
             .gap_2()
             .child(NAME)
+            .child(self.number_content(cx))
             .child(self.select_content())
+            .child(self.preview_content(cx))
             .child(
                 Button::new("calc")
                     .label("开始计算")
@@ -130,28 +237,15 @@ impl Render for LiuYaoContent {
 
 impl QiGuaCore for LiuYaoContent {
     fn calc_gua(&mut self, cx: &mut Context<Self>) {
-        let first = self.first_yao.read(cx).yao.clone().unwrap();
-        let second = self.second_yao.read(cx).yao.clone().unwrap();
-        let third = self.third_yao.read(cx).yao.clone().unwrap();
-        let fourth = self.fourth_yao.read(cx).yao.clone().unwrap();
-        let fifth = self.fifth_yao.read(cx).yao.clone().unwrap();
-        let sixth = self.sixth_yao.read(cx).yao.clone().unwrap();
-
-        // 计算本卦
-        let ben_gua = ben_gua(first, second, third, fourth, fifth, sixth);
-
-        // 计算变卦
-        // 需要检查每一个爻，是否是动爻，对动幺进行翻转
-        let bian_gua = bian_gua(&ben_gua, first, second, third, fourth, fifth, sixth);
-
-        // 计算互卦
-        // 互卦
-        let hu_gua = ben_gua.hu_gua();
+        // 从初爻到上爻，本卦和动爻由六个爻得出，变卦和互卦由结果得出
+        let Some(liu_yao_list) = self.liu_yao_list(cx) else {
+            return;
+        };
 
-        let ba_gua_result = GuaResult::new(ben_gua, bian_gua, hu_gua);
+        let ba_gua_result =
+            GuaResult::from_liu_yao(liu_yao_list, QiGuaSource::LiuYao(liu_yao_list));
 
-        let gua_result = GlobalState::state_mut(cx);
-        gua_result.result = Some(ba_gua_result.clone());
+        GlobalState::save_result(cx, ba_gua_result);
 
         cx.notify();
     }
@@ -161,69 +255,6 @@ impl QiGuaCore for LiuYaoContent {
     }
 }
 
-/// 爻的类型
-///
-/// 用于六爻起卦，所以需要四种
-#[derive(Debug, PartialEq, EnumString, Display, Clone, Copy, IntoStaticStr)]
-enum LiuYaoType {
-    /// 阴
-    #[strum(serialize = "阴")]
-    阴,
-    /// 阳
-    #[strum(serialize = "阳")]
-    阳,
-    /// 动阴
-    #[strum(serialize = "动阴")]
-    动阴,
-    /// 动阳
-    #[strum(serialize = "动阳")]
-    动阳,
-}
-
-impl LiuYaoType {
-    /// 转换为 Yao
-    ///
-    /// * `阴` 和 `动阴` 转为 `阴`
-    /// * `阳` 和 `动阳` 转为 `阳`
-    pub const fn yao(self) -> Yao {
-        match self {
-            LiuYaoType::阴 | LiuYaoType::动阴 => Yao::阴,
-            LiuYaoType::阳 | LiuYaoType::动阳 => Yao::阳,
-        }
-    }
-
-    /// 是否是动幺
-    pub fn is_dong(self) -> bool {
-        match self {
-            LiuYaoType::动阴 | LiuYaoType::动阳 => true,
-            _ => false,
-        }
-    }
-
-    /// 从三个 yao 转换
-    ///
-    /// # 注意
-    /// * 哪种爻数量多，最终就是哪种爻
-    /// * 三个爻一样，就是动爻
-    pub fn from_three_yao(yao1: Yao, yao2: Yao, yao3: Yao) -> Self {
-        match (yao1, yao2, yao3) {
-            (Yao::阳, Yao::阳, Yao::阳) => LiuYaoType::动阳,
-            (Yao::阴, Yao::阴, Yao::阴) => LiuYaoType::动阴,
-            (Yao::阳, Yao::阴, Yao::阴) => LiuYaoType::阴,
-            (Yao::阴, Yao::阳, Yao::阴) => LiuYaoType::阴,
-            (Yao::阴, Yao::阴, Yao::阳) => LiuYaoType::阴,
-            (Yao::阴, Yao::阳, Yao::阳) => LiuYaoType::阳,
-            (Yao::阳, Yao::阴, Yao::阳) => LiuYaoType::阳,
-            (Yao::阳, Yao::阳, Yao::阴) => LiuYaoType::阳,
-        }
-    }
-
-    /// 随机生成 LiuYaoType
-    pub fn random() -> Self {
-        Self::from_three_yao(Yao::random_yao(), Yao::random_yao(), Yao::random_yao())
-    }
-}
-
 /// 单个爻的选择（以及随机生成）UI
 struct SingalYaoSelect {
     /// 选择的爻
@@ -273,6 +304,20 @@ impl SingalYaoSelect {
     }
 }
 
+impl SingalYaoSelect {
+    /// 选中指定的爻
+    fn set_yao(&mut self, yao: LiuYaoType, window: &mut Window, cx: &mut Context<Self>) {
+        self.select_state.update(cx, |state, cx| {
+            state.set_selected_value(&yao.into(), window, cx);
+        });
+
+        self.is_selected = true;
+        self.yao = Some(yao);
+
+        cx.notify();
+    }
+}
+
 impl Render for SingalYaoSelect {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         h_flex()
@@ -284,132 +329,8 @@ impl Render for SingalYaoSelect {
                 Button::new("random-yao")
                     .icon(Icon::empty().path("icons/dices.svg"))
                     .on_click(cx.listener(|this, _, window, cx| {
-                        let random = LiuYaoType::random();
-                        this.select_state.update(cx, |state, cx| {
-                            state.set_selected_value(&random.into(), window, cx);
-
-                            this.is_selected = true;
-                            this.yao = Some(random);
-                        });
+                        this.set_yao(LiuYaoType::random(), window, cx);
                     })),
             )
     }
 }
-
-/// 获取变卦
-///
-/// 当和本卦相同的时候返回 None
-fn bian_gua(
-    ben_gua: &Gua64,
-    first: LiuYaoType,
-    second: LiuYaoType,
-    third: LiuYaoType,
-    fourth: LiuYaoType,
-    fifth: LiuYaoType,
-    sixth: LiuYaoType,
-) -> Option<Gua64> {
-    let mut bian_gua = ben_gua.clone();
-
-    let is_dong = first.is_dong();
-    if is_dong {
-        bian_gua.change(Gua64YaoIndex::First);
-    }
-
-    let is_dong = second.is_dong();
-    if is_dong {
-        bian_gua.change(Gua64YaoIndex::Second);
-    }
-
-    let is_dong = third.is_dong();
-    if is_dong {
-        bian_gua.change(Gua64YaoIndex::Third);
-    }
-
-    let is_dong = fourth.is_dong();
-    if is_dong {
-        bian_gua.change(Gua64YaoIndex::Fourth);
-    }
-
-    let is_dong = fifth.is_dong();
-    if is_dong {
-        bian_gua.change(Gua64YaoIndex::Fifth);
-    }
-
-    let is_dong = sixth.is_dong();
-    if is_dong {
-        bian_gua.change(Gua64YaoIndex::Sixth);
-    }
-
-    match ben_gua.eq(&bian_gua) {
-        true => None,
-        false => Some(bian_gua),
-    }
-}
-
-/// 获取本卦
-fn ben_gua(
-    first: LiuYaoType,
-    second: LiuYaoType,
-    third: LiuYaoType,
-    fourth: LiuYaoType,
-    fifth: LiuYaoType,
-    sixth: LiuYaoType,
-) -> Gua64 {
-    let shang = Gua8::new(
-        LiuYaoType::yao(fourth),
-        LiuYaoType::yao(fifth),
-        LiuYaoType::yao(sixth),
-    );
-    let xia = Gua8::new(
-        LiuYaoType::yao(first),
-        LiuYaoType::yao(second),
-        LiuYaoType::yao(third),
-    );
-
-    let ben_gua = Gua64::new(shang, xia);
-
-    ben_gua
-}
-
-mod tests {
-    use super::*;
-
-    #[test]
-    /// 测试六爻算卦结果
-    fn test_liu_yao() {
-        let first = LiuYaoType::阳;
-        let second = LiuYaoType::阴;
-        let third = LiuYaoType::动阳;
-        let fourth = LiuYaoType::动阴;
-        let fifth = LiuYaoType::阳;
-        let sixth = LiuYaoType::阳;
-
-        // 计算本卦
-        let ben_gua_result = ben_gua(first, second, third, fourth, fifth, sixth);
-
-        // 计算变卦
-        let bian_gua_result = bian_gua(&ben_gua_result, first, second, third, fourth, fifth, sixth);
-
-        let hu_gua_result = ben_gua_result.hu_gua();
-
-        assert_eq!(ben_gua_result, Gua64::家人);
-        assert_eq!(bian_gua_result.unwrap(), Gua64::无妄);
-        assert_eq!(hu_gua_result, Gua64::未济);
-
-        // 测试没有变卦的结果
-        let third = LiuYaoType::阳;
-        let fourth = LiuYaoType::阴;
-
-        // 计算本卦
-        let ben_gua_result = ben_gua(first, second, third, fourth, fifth, sixth);
-
-        // 计算变卦
-        let bian_gua_result = bian_gua(&ben_gua_result, first, second, third, fourth, fifth, sixth);
-
-        let hu_gua_result = ben_gua_result.hu_gua();
-
-        assert_eq!(ben_gua_result, Gua64::家人);
-        assert_eq!(bian_gua_result.is_none(), true);
-        assert_eq!(hu_gua_result, Gua64::未济);
-    }
-}
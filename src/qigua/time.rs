@@ -1,18 +1,23 @@
-use chinese_lunisolar_calendar::{EarthlyBranch, LunisolarDate, SolarDate};
-use chrono::{Datelike, Local, NaiveDateTime, Timelike};
+use chinese_lunisolar_calendar::{LunisolarDate, SolarDate};
+use chrono::{Datelike, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike};
 use gpui::{
     App, AppContext, Context, Entity, IntoElement, ParentElement, Render, SharedString, Styled,
-    Subscription, Window, div,
+    Subscription, Window, div, prelude::FluentBuilder,
 };
 use gpui_component::{
-    IndexPath, StyledExt,
+    ActiveTheme, Disableable, IndexPath, StyledExt,
     button::{Button, ButtonVariants},
     date_picker::{DatePicker, DatePickerEvent, DatePickerState},
     select::{Select, SelectEvent, SelectState},
 };
 
 use crate::{
-    core::ba_gua::{BaGuaCalculator, GuaResult},
+    core::{
+        ba_gua::{BaGuaCalculator, GuaResult, QiGuaSource},
+        error::CoreError,
+        gan_zhi::DiZhi,
+        jie_qi::JieQi,
+    },
     qigua::core::QiGuaCore,
     state::global::GlobalState,
 };
@@ -47,6 +52,8 @@ pub struct TimeContent {
     date_picker: Entity<DatePickerState>,
     select_time: NaiveDateTime,
     hour_select_state: Entity<SelectState<Vec<&'static str>>>,
+    /// 计算失败时的提示
+    error: Option<String>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -81,17 +88,9 @@ impl TimeContent {
             cx.subscribe(&date_picker, |this, _, ev, _| match ev {
                 DatePickerEvent::Change(date) => {
                     if let Some(d) = date.start() {
-                        let year = d.year();
-                        let month = d.month();
-                        let day = d.day();
-
-                        // 更新选择的日期
-                        this.select_time = this
-                            .select_time
-                            .with_year(year)
-                            .and_then(|t| t.with_month(month))
-                            .and_then(|t| t.with_day(day))
-                            .unwrap();
+                        // 更新选择的日期，保留原来的时间
+                        this.select_time = d.and_time(this.select_time.time());
+                        this.error = None;
                     }
                 }
             }),
@@ -99,10 +98,12 @@ impl TimeContent {
                 &hour_select_state,
                 |this, _, event: &SelectEvent<Vec<&'static str>>, _| match event {
                     SelectEvent::Confirm(value) => {
-                        this.select_time = this
-                            .select_time
-                            .with_hour(value.unwrap().parse::<u32>().unwrap())
-                            .unwrap()
+                        if let Some(time) = value
+                            .and_then(|hour| hour.parse::<u32>().ok())
+                            .and_then(|hour| this.select_time.with_hour(hour))
+                        {
+                            this.select_time = time;
+                        }
                     }
                 },
             ),
@@ -112,6 +113,7 @@ impl TimeContent {
             date_picker,
             select_time,
             hour_select_state,
+            error: None,
             _subscriptions,
         }
     }
@@ -125,6 +127,23 @@ impl TimeContent {
     fn huor_select_content(&self) -> impl IntoElement {
         Select::new(&self.hour_select_state).title_prefix("时间：")
     }
+
+    /// 所选时间所处的节气，以及节气开始的时间
+    fn jie_qi_text(&self) -> String {
+        let current = Local
+            .from_local_datetime(&self.select_time)
+            .earliest()
+            .and_then(|time| JieQi::current(&time));
+
+        match current {
+            Some((jie_qi, start)) => format!(
+                "节气: {}（{} 起）",
+                jie_qi,
+                start.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+            ),
+            None => "节气: 超出可计算的年份".to_string(),
+        }
+    }
 }
 
 impl Render for TimeContent {
@@ -136,12 +155,14 @@ impl Render for TimeContent {
         let day = self.select_time.day();
         let hour = self.select_time.hour();
 
-        let shi_chen = hour_to_shi_chen(hour);
+        let shi_chen = DiZhi::from_hour(hour);
 
-        let lunisolar_date = LunisolarDate::from_solar_date(
-            SolarDate::from_ymd(year as u16, month as u8, day as u8).unwrap(),
-        )
-        .unwrap();
+        // 超出范围时不能计算，在农历处提示
+        let lunisolar_date = lunisolar_date(self.select_time.date());
+        let lunisolar_text = match &lunisolar_date {
+            Ok(date) => div().child(format!("农历: {}", date)),
+            Err(err) => div().text_color(cx.theme().danger).child(err.to_string()),
+        };
 
         div()
             .w_80()
@@ -152,12 +173,17 @@ impl Render for TimeContent {
             .child(self.data_picker_content())
             .child(self.huor_select_content())
             .child(format!("公历: {}-{}-{} {}", year, month, day, hour))
-            .child(format!("农历: {}", lunisolar_date))
+            .child(lunisolar_text)
             .child(format!("时辰: {}", shi_chen))
+            .child(self.jie_qi_text())
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_color(cx.theme().danger).child(error))
+            })
             .child(
                 Button::new("calc")
                     .label("开始计算")
                     .primary()
+                    .disabled(lunisolar_date.is_err())
                     .on_click(move |_, _, cx| {
                         cx.update_entity(
                             &entity,
@@ -174,24 +200,20 @@ impl QiGuaCore for TimeContent {
     fn calc_gua(&mut self, cx: &mut Context<Self>) {
         let date = self.select_time;
 
-        let year = date.year();
-        let month = date.month();
-        let day = date.day();
-        let hour = date.hour();
-
-        // 农历
-        let lunisolar_date = LunisolarDate::from_solar_date(
-            SolarDate::from_ymd(year as u16, month as u8, day as u8).unwrap(),
-        )
-        .unwrap();
-
         // 时辰
-        let shi_chen = hour_to_shi_chen(hour);
+        let shi_chen = DiZhi::from_hour(date.hour());
+
+        let result = lunisolar_date(date.date())
+            .and_then(|lunisolar_date| time_to_gua(date, lunisolar_date, shi_chen));
 
-        let ba_gua_result = time_to_gua(lunisolar_date, shi_chen);
+        match result {
+            Ok(ba_gua_result) => {
+                self.error = None;
 
-        let gua_result = GlobalState::state_mut(cx);
-        gua_result.result = Some(ba_gua_result.clone());
+                GlobalState::save_result(cx, ba_gua_result);
+            }
+            Err(err) => self.error = Some(err.to_string()),
+        }
 
         cx.notify();
     }
@@ -201,53 +223,95 @@ impl QiGuaCore for TimeContent {
 }
 
 /// 根据时间计算卦象
-fn time_to_gua(lunisolar_date: LunisolarDate, shi_chen: EarthlyBranch) -> GuaResult {
+///
+/// * `date` - 选择的公历时间，只用于记录起卦来源
+fn time_to_gua(
+    date: NaiveDateTime,
+    lunisolar_date: LunisolarDate,
+    shi_chen: DiZhi,
+) -> Result<GuaResult, CoreError> {
     let day = lunisolar_date.to_lunar_day().to_u8();
     let month = lunisolar_date.to_lunar_month().to_u8_raw();
-    let year = lunisolar_date.to_lunar_year().to_earthly_branch().ordinal();
+    let year_branch = DiZhi::from(lunisolar_date.to_lunar_year().to_earthly_branch());
 
-    let shang_num = (year + month + day) as u16;
-    let xia_num = shang_num + shi_chen.ordinal() as u16;
+    let shang_num = (year_branch.number() + month + day) as u64;
+    let xia_num = shang_num + shi_chen.number() as u64;
 
-    BaGuaCalculator::calculate_from_two_numbers(shang_num, xia_num, xia_num)
+    let source = QiGuaSource::Time {
+        date,
+        lunar_date: lunisolar_date.to_string(),
+        year_branch,
+        lunar_month: month,
+        lunar_day: day,
+        shi_chen,
+    };
+
+    BaGuaCalculator::calculate_from_two_numbers(shang_num, xia_num, xia_num, source)
 }
 
-/// 根据小时获取时辰
-fn hour_to_shi_chen(hour: u32) -> EarthlyBranch {
-    match hour {
-        23 | 0 => EarthlyBranch::First,     // 子时: 23:00-01:00
-        1 | 2 => EarthlyBranch::Second,     // 丑时: 01:00-03:00
-        3 | 4 => EarthlyBranch::Third,      // 寅时: 03:00-05:00
-        5 | 6 => EarthlyBranch::Fourth,     // 卯时: 05:00-07:00
-        7 | 8 => EarthlyBranch::Fifth,      // 辰时: 07:00-09:00
-        9 | 10 => EarthlyBranch::Sixth,     // 巳时: 09:00-11:00
-        11 | 12 => EarthlyBranch::Seventh,  // 午时: 11:00-13:00
-        13 | 14 => EarthlyBranch::Eighth,   // 未时: 13:00-15:00
-        15 | 16 => EarthlyBranch::Ninth,    // 申时: 15:00-17:00
-        17 | 18 => EarthlyBranch::Tenth,    // 酉时: 17:00-19:00
-        19 | 20 => EarthlyBranch::Eleventh, // 戌时: 19:00-21:00
-        21 | 22 => EarthlyBranch::Twelfth,  // 亥时: 21:00-23:00
-        _ => unreachable!("hour must be 0-23"),
-    }
+/// 公历日期转为农历，只支持 1901 年到 2100 年
+fn lunisolar_date(date: NaiveDate) -> Result<LunisolarDate, CoreError> {
+    let solar_date = u16::try_from(date.year())
+        .ok()
+        .and_then(|year| SolarDate::from_ymd(year, date.month() as u8, date.day() as u8).ok())
+        .ok_or(CoreError::DateOutOfRange(date))?;
+
+    LunisolarDate::from_solar_date(solar_date).map_err(|_| CoreError::DateOutOfRange(date))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::core::models::Gua64;
-    use chinese_lunisolar_calendar::{EarthlyBranch, LunisolarDate, SolarDate};
+    use crate::core::{ba_gua::QiGuaSource, gan_zhi::DiZhi, models::Gua64};
+    use chinese_lunisolar_calendar::{LunisolarDate, SolarDate};
+    use chrono::NaiveDate;
 
-    use super::time_to_gua;
+    use super::{lunisolar_date, time_to_gua};
 
     #[test]
     /// 测试 time_to_gua
     fn test_time_to_gua() {
+        let date = NaiveDate::from_ymd_opt(2025, 11, 22)
+            .unwrap()
+            .and_hms_opt(8, 0, 0)
+            .unwrap();
+
         let r1 = time_to_gua(
+            date,
             LunisolarDate::from_solar_date(SolarDate::from_ymd(2025, 11, 22).unwrap()).unwrap(),
-            EarthlyBranch::Fifth,
-        );
+            DiZhi::辰,
+        )
+        .unwrap();
 
         assert_eq!(r1.ben_gua, Gua64::晋);
-        assert_eq!(r1.bian_gua.unwrap(), Gua64::豫);
+        assert_eq!(r1.bian_gua().unwrap(), Gua64::豫);
         assert_eq!(r1.hu_gua, Gua64::蹇);
+
+        // 巳年十月初三辰时
+        match r1.source {
+            QiGuaSource::Time {
+                year_branch,
+                lunar_month,
+                lunar_day,
+                shi_chen,
+                ..
+            } => {
+                assert_eq!(year_branch, DiZhi::巳);
+                assert_eq!(lunar_month, 10);
+                assert_eq!(lunar_day, 3);
+                assert_eq!(shi_chen, DiZhi::辰);
+            }
+            _ => panic!("起卦方式应为时间"),
+        }
+    }
+
+    #[test]
+    /// 超出农历范围的日期返回错误
+    fn test_lunisolar_date() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert!(lunisolar_date(date(2025, 11, 22)).is_ok());
+        assert!(lunisolar_date(date(1900, 6, 1)).is_err());
+        assert!(lunisolar_date(date(2101, 6, 1)).is_err());
+        assert!(lunisolar_date(date(-100, 6, 1)).is_err());
     }
 }
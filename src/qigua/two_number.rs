@@ -1,18 +1,28 @@
+use chrono::{Local, Timelike};
 use gpui::{
     App, AppContext, Context, Entity, IntoElement, ParentElement, Render, SharedString, Styled,
-    Window, div,
+    Subscription, Window, div, prelude::FluentBuilder,
 };
 use gpui_component::{
-    StyledExt,
+    ActiveTheme, IndexPath, StyledExt,
     button::{Button, ButtonVariants},
     input::{Input, InputState},
+    select::{Select, SelectEvent, SelectState},
 };
 
-use crate::{core::ba_gua::BaGuaCalculator, qigua::core::QiGuaCore, state::global::GlobalState};
+use crate::{
+    core::{
+        ba_gua::{BaGuaCalculator, DongYaoFormula, DongYaoFormulaKind},
+        gan_zhi::DiZhi,
+        models::{ShuZi, ShuZiMode},
+    },
+    qigua::core::QiGuaCore,
+    state::global::GlobalState,
+};
 
-const NAME: &str = "两个数字";
+const NAME: &str = "数字起卦";
 
-/// 两个数字起卦
+/// 数字起卦
 pub struct TwoNumber {
     content: Entity<InputTwoNumContent>,
 }
@@ -35,10 +45,21 @@ impl Render for TwoNumber {
     }
 }
 
-/// 输入两个数字来计算卦象
+/// 输入两个数字来计算卦象，按所选公式还可以输入第三个数
 pub struct InputTwoNumContent {
     input1_state: Entity<InputState>,
     input2_state: Entity<InputState>,
+    /// 三数起卦时的第三个数
+    input3_state: Entity<InputState>,
+    mode_select_state: Entity<SelectState<Vec<&'static str>>>,
+    /// 所选的取数方式
+    mode: ShuZiMode,
+    formula_select_state: Entity<SelectState<Vec<&'static str>>>,
+    /// 所选变爻公式的种类，时辰和第三个数在计算时填入
+    formula_kind: DongYaoFormulaKind,
+    /// 计算失败时的提示
+    error: Option<String>,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl InputTwoNumContent {
@@ -47,12 +68,87 @@ impl InputTwoNumContent {
     }
 
     fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
-        let input1_state = cx.new(|cx| InputState::new(window, cx).placeholder("输入数字1"));
-        let input2_state = cx.new(|cx| InputState::new(window, cx).placeholder("输入数字2"));
+        let input1_state =
+            cx.new(|cx| InputState::new(window, cx).placeholder("输入数字1，可以是手机号等长数字"));
+        let input2_state =
+            cx.new(|cx| InputState::new(window, cx).placeholder("输入数字2，可以是手机号等长数字"));
+        let input3_state = cx.new(|cx| InputState::new(window, cx).placeholder("输入数字3"));
+
+        let mode_select_state = cx.new(|cx| {
+            let names = ShuZiMode::ALL.map(<&'static str>::from).to_vec();
+
+            SelectState::new(names, Some(IndexPath::new(0)), window, cx)
+        });
+
+        let formula_select_state = cx.new(|cx| {
+            let names = DongYaoFormulaKind::ALL.map(|kind| kind.name()).to_vec();
+
+            SelectState::new(names, Some(IndexPath::new(0)), window, cx)
+        });
+
+        let _subscriptions = vec![
+            cx.subscribe(
+                &mode_select_state,
+                |this, _, event: &SelectEvent<Vec<&'static str>>, _| match event {
+                    SelectEvent::Confirm(value) => {
+                        if let Some(mode) = value.and_then(|name| {
+                            ShuZiMode::ALL
+                                .into_iter()
+                                .find(|mode| <&'static str>::from(*mode) == name)
+                        }) {
+                            this.mode = mode;
+                        }
+                    }
+                },
+            ),
+            cx.subscribe(
+                &formula_select_state,
+                |this, _, event: &SelectEvent<Vec<&'static str>>, cx| match event {
+                    SelectEvent::Confirm(value) => {
+                        if let Some(kind) = value.and_then(|name| {
+                            DongYaoFormulaKind::ALL
+                                .into_iter()
+                                .find(|kind| kind.name() == name)
+                        }) {
+                            this.formula_kind = kind;
+                            this.error = None;
+
+                            cx.notify();
+                        }
+                    }
+                },
+            ),
+        ];
 
         Self {
             input1_state,
             input2_state,
+            input3_state,
+            mode_select_state,
+            mode: ShuZiMode::default(),
+            formula_select_state,
+            formula_kind: DongYaoFormulaKind::default(),
+            error: None,
+            _subscriptions,
+        }
+    }
+
+    /// 是否需要输入第三个数
+    fn need_third(&self) -> bool {
+        self.formula_kind.is_san_shu()
+    }
+
+    /// 根据所选的公式生成变爻公式，第三个数缺失时返回错误提示
+    fn formula(&self, cx: &App) -> Result<DongYaoFormula, String> {
+        let third = || read_shu_zi(&self.input3_state, "数字3", cx);
+
+        match self.formula_kind {
+            DongYaoFormulaKind::Sum => Ok(DongYaoFormula::Sum),
+            DongYaoFormulaKind::SumWithShiChen => Ok(DongYaoFormula::SumWithShiChen(
+                DiZhi::from_hour(Local::now().hour()),
+            )),
+            DongYaoFormulaKind::Third => third().map(DongYaoFormula::Third),
+            DongYaoFormulaKind::SumOfThree => third().map(DongYaoFormula::SumOfThree),
         }
     }
 }
@@ -68,6 +164,14 @@ impl Render for InputTwoNumContent {
             .child(NAME)
             .child(Input::new(&self.input1_state))
             .child(Input::new(&self.input2_state))
+            .child(Select::new(&self.mode_select_state).title_prefix("取数："))
+            .child(Select::new(&self.formula_select_state).title_prefix("变爻："))
+            .when(self.need_third(), |this| {
+                this.child(Input::new(&self.input3_state))
+            })
+            .when_some(self.error.clone(), |this, error| {
+                this.child(div().text_color(cx.theme().danger).child(error))
+            })
             .child(
                 Button::new("calc")
                     .label("开始计算")
@@ -87,14 +191,27 @@ impl Render for InputTwoNumContent {
 
 impl QiGuaCore for InputTwoNumContent {
     fn calc_gua(&mut self, cx: &mut Context<Self>) {
-        let shang_num: u16 = self.input1_state.read(cx).value().parse().unwrap_or(0);
-        let xia_num: u16 = self.input2_state.read(cx).value().parse().unwrap_or(0);
-
-        let ba_gua_result =
-            BaGuaCalculator::calculate_from_two_numbers(shang_num, xia_num, shang_num + xia_num);
-
-        let gua_result = GlobalState::state_mut(cx);
-        gua_result.result = Some(ba_gua_result.clone());
+        // 输入不合法时提示，而不是按 0 起卦
+        let input = read_shu_zi(&self.input1_state, "数字1", cx).and_then(|shang_num| {
+            let xia_num = read_shu_zi(&self.input2_state, "数字2", cx)?;
+            let formula = self.formula(cx)?;
+
+            Ok((shang_num, xia_num, formula))
+        });
+
+        let result = input.and_then(|(shang_num, xia_num, formula)| {
+            BaGuaCalculator::calculate_from_numbers(shang_num, xia_num, self.mode, formula)
+                .map_err(|err| err.to_string())
+        });
+
+        match result {
+            Ok(ba_gua_result) => {
+                self.error = None;
+
+                GlobalState::save_result(cx, ba_gua_result);
+            }
+            Err(err) => self.error = Some(err),
+        }
 
         cx.notify();
     }
@@ -102,3 +219,12 @@ impl QiGuaCore for InputTwoNumContent {
         NAME.into()
     }
 }
+
+/// 读取并校验输入的数字，出错时的提示带上输入框的名称
+fn read_shu_zi(state: &Entity<InputState>, label: &str, cx: &App) -> Result<ShuZi, String> {
+    state
+        .read(cx)
+        .value()
+        .parse()
+        .map_err(|err| format!("{label}：{err}"))
+}
@@ -0,0 +1,229 @@
+use gpui::{
+    App, AppContext, Context, Div, Entity, IntoElement, ParentElement, Render, SharedString,
+    Styled, Window, div, px,
+};
+use gpui_component::{
+    ActiveTheme, Disableable, StyledExt,
+    button::{Button, ButtonVariants},
+    h_flex, v_flex,
+};
+
+use crate::{
+    core::{
+        ba_gua::{GuaResult, QiGuaSource},
+        models::Gua64YaoIndex,
+        yao_gua::{CoinSide, CoinToss},
+    },
+    qigua::core::QiGuaCore,
+    state::global::GlobalState,
+    ui::gua_xiang::GuaXiang,
+};
+
+const NAME: &str = "摇卦";
+
+/// 三枚铜钱摇卦
+pub struct YaoGua {
+    content: Entity<YaoGuaContent>,
+}
+
+impl YaoGua {
+    pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
+        cx.new(|cx| Self::new(window, cx))
+    }
+
+    fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let content = YaoGuaContent::view(window, cx);
+
+        Self { content }
+    }
+}
+
+impl Render for YaoGua {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        div().p_2().child(self.content.clone())
+    }
+}
+
+/// 依次投掷铜钱来计算卦象
+pub struct YaoGuaContent {
+    /// 已投掷的结果，从初爻开始
+    toss_list: Vec<CoinToss>,
+}
+
+impl YaoGuaContent {
+    pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
+        cx.new(|cx| Self::new(window, cx))
+    }
+
+    fn new(_: &mut Window, _: &mut Context<Self>) -> Self {
+        Self {
+            toss_list: Vec::new(),
+        }
+    }
+
+    /// 投掷下一爻
+    fn toss(&mut self) {
+        if self.toss_list.len() < 6 {
+            self.toss_list.push(CoinToss::random());
+        }
+    }
+
+    /// 一键摇卦，从初爻开始重新投掷六次
+    fn toss_all(&mut self) {
+        self.toss_list = (0..6).map(|_| CoinToss::random()).collect();
+    }
+
+    /// 六次投掷的结果，未完成时为 `None`
+    fn toss_array(&self) -> Option<[CoinToss; 6]> {
+        self.toss_list.clone().try_into().ok()
+    }
+
+    /// 一枚铜钱
+    fn coin_content(&self, side: CoinSide, cx: &mut Context<Self>) -> Div {
+        let color = match side {
+            CoinSide::字 => cx.theme().foreground,
+            CoinSide::背 => cx.theme().muted_foreground,
+        };
+
+        div()
+            .size(px(24.))
+            .flex()
+            .items_center()
+            .justify_center()
+            .rounded_full()
+            .border_1()
+            .border_color(cx.theme().border)
+            .text_color(color)
+            .child(side.to_string())
+    }
+
+    /// 投掷记录，上爻在上，初爻在下
+    fn toss_list_content(&self, cx: &mut Context<Self>) -> Div {
+        let rows = Gua64YaoIndex::ALL
+            .into_iter()
+            .enumerate()
+            .rev()
+            .map(|(index, yao_index)| {
+                let row = h_flex().gap_2().child(yao_index.name());
+
+                match self.toss_list.get(index) {
+                    Some(toss) => row
+                        .children(toss.0.map(|side| self.coin_content(side, cx)))
+                        .child(format!("{}，{}", toss.number(), toss.liu_yao_type())),
+                    None => row.child(div().text_color(cx.theme().muted_foreground).child("未摇")),
+                }
+            });
+
+        v_flex().gap_1().children(rows.collect::<Vec<_>>())
+    }
+
+    /// 六爻摇完后预览卦象
+    fn preview_content(&self) -> Div {
+        match self.toss_array() {
+            Some(toss_list) => {
+                let result = GuaResult::from_liu_yao(
+                    toss_list.map(|toss| toss.liu_yao_type()),
+                    QiGuaSource::YaoGua(toss_list),
+                );
+
+                h_flex()
+                    .gap_2()
+                    .child(
+                        GuaXiang::gua64(&result.ben_gua)
+                            .highlight(&result.dong_yao)
+                            .size(px(48.)),
+                    )
+                    .child(result.ben_gua.name())
+            }
+            None => h_flex(),
+        }
+    }
+}
+
+impl Render for YaoGuaContent {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entity = cx.entity();
+        let is_done = self.toss_list.len() == 6;
+
+        div()
+            .w_80()
+            .p_2()
+            .v_flex()
+            .gap_2()
+            .child(NAME)
+            .child(
+                div()
+                    .text_color(cx.theme().muted_foreground)
+                    .child("三枚铜钱掷六次，从初爻开始。字记二，背记三"),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new("toss")
+                            .outline()
+                            .label("摇一次")
+                            .disabled(is_done)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.toss();
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        Button::new("toss-all")
+                            .outline()
+                            .label("一键摇卦")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.toss_all();
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        Button::new("toss-reset")
+                            .ghost()
+                            .label("重新开始")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.toss_list.clear();
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .child(self.toss_list_content(cx))
+            .child(self.preview_content())
+            .child(
+                Button::new("calc")
+                    .label("开始计算")
+                    .primary()
+                    .disabled(!is_done)
+                    .on_click(move |_, _, cx| {
+                        cx.update_entity(
+                            &entity,
+                            |input: &mut YaoGuaContent, context: &mut Context<YaoGuaContent>| {
+                                input.calc_gua(context)
+                            },
+                        );
+                    }),
+            )
+    }
+}
+
+impl QiGuaCore for YaoGuaContent {
+    fn calc_gua(&mut self, cx: &mut Context<Self>) {
+        let Some(toss_list) = self.toss_array() else {
+            return;
+        };
+
+        let ba_gua_result = GuaResult::from_liu_yao(
+            toss_list.map(|toss| toss.liu_yao_type()),
+            QiGuaSource::YaoGua(toss_list),
+        );
+
+        GlobalState::save_result(cx, ba_gua_result);
+
+        cx.notify();
+    }
+
+    fn name() -> SharedString {
+        NAME.into()
+    }
+}
@@ -1 +1,2 @@
 pub mod global;
+pub mod history;
@@ -2,7 +2,9 @@ use gpui::{App, Global};
 
 use crate::{
     assets::init_gua64_info,
-    core::{ba_gua::GuaResult, basic::Gua64Info},
+    core::{ba_gua::GuaResult, basic::Gua64Info, models::Gua64},
+    state::history::HistoryList,
+    ui::sidebar::StageItem,
 };
 
 pub struct GlobalState {
@@ -10,17 +12,46 @@ pub struct GlobalState {
     pub result: Option<GuaResult>,
     /// 64卦信息
     pub gua64_info_list: Vec<Gua64Info>,
+    /// 加载64卦信息失败的原因，在主界面顶部提示，关闭后清除
+    pub gua64_info_error: Option<String>,
+    /// 算卦历史记录
+    pub history: HistoryList,
+    /// 加载历史记录时遇到的问题，如文件损坏已备份
+    pub history_load_error: Option<String>,
+    /// 最近一次保存历史记录失败的原因，保存成功后清除
+    pub history_save_error: Option<String>,
+    /// 当前展示的页面
+    pub active_stage: StageItem,
+    /// 资源页需要展示的卦象
+    pub library_gua: Option<Gua64>,
 }
 
 impl Global for GlobalState {}
 
 impl GlobalState {
     pub fn init(cx: &mut App) {
-        let gua64_info_list = init_gua64_info();
+        let (gua64_info_list, gua64_info_error) = match init_gua64_info() {
+            Ok(list) => (list, None),
+            Err(err) => (
+                Vec::new(),
+                Some(format!(
+                    "加载六十四卦资料失败，卦辞和爻辞将无法显示：{err:#}"
+                )),
+            ),
+        };
+
+        let (history, history_load_error) = HistoryList::load();
+        let history_load_error = history_load_error.map(|err| format!("加载历史记录失败：{err:#}"));
 
         cx.set_global::<GlobalState>(GlobalState {
             result: None,
             gua64_info_list,
+            gua64_info_error,
+            history,
+            history_load_error,
+            history_save_error: None,
+            active_stage: StageItem::QiGua,
+            library_gua: None,
         });
     }
 
@@ -31,4 +62,40 @@ impl GlobalState {
     pub fn state_mut(cx: &mut App) -> &mut Self {
         cx.global_mut::<GlobalState>()
     }
+
+    /// 保存算卦结果，并写入历史记录
+    pub fn save_result(cx: &mut App, result: GuaResult) {
+        let state = Self::state_mut(cx);
+
+        state.result = Some(result.clone());
+
+        let saved = state.history.add(result);
+        state.set_history_save_result(saved);
+    }
+
+    /// 删除一条历史记录
+    pub fn remove_history(cx: &mut App, id: u64) {
+        let state = Self::state_mut(cx);
+
+        let saved = state.history.remove(id);
+        state.set_history_save_result(saved);
+    }
+
+    /// 记录保存历史记录的结果，失败时在界面上提示
+    fn set_history_save_result(&mut self, saved: anyhow::Result<()>) {
+        self.history_save_error = saved.err().map(|err| format!("保存历史记录失败：{err:#}"));
+    }
+
+    /// 关闭加载64卦信息失败的提示
+    pub fn dismiss_gua64_info_error(cx: &mut App) {
+        Self::state_mut(cx).gua64_info_error = None;
+    }
+
+    /// 跳转到资源页，并展示指定的卦象
+    pub fn open_library(cx: &mut App, gua: &Gua64) {
+        let state = Self::state_mut(cx);
+
+        state.library_gua = Some(gua.clone());
+        state.active_stage = StageItem::Library;
+    }
 }
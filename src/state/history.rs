@@ -0,0 +1,185 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Error, Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+use crate::core::ba_gua::GuaResult;
+
+/// 历史记录文件名
+const HISTORY_FILE_NAME: &str = "history.json";
+
+/// 单条算卦记录
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    /// 记录编号，用于删除
+    pub id: u64,
+    /// 算卦结果，包含起卦方式和原始输入
+    pub result: GuaResult,
+}
+
+/// 算卦历史记录，保存在本地磁盘
+pub struct HistoryList {
+    /// 记录文件路径
+    path: Option<PathBuf>,
+    /// 所有记录，按时间从旧到新排列
+    records: Vec<HistoryRecord>,
+}
+
+impl HistoryList {
+    /// 从本地文件加载历史记录
+    ///
+    /// 文件不存在时返回空记录。文件无法读取或解析时，先改名备份再从空记录开始，
+    /// 之后的记录仍写入原来的位置；无法备份时不再写入，避免覆盖原来的文件。
+    /// 加载中遇到的问题随记录一起返回，用于提示
+    pub fn load() -> (Self, Option<Error>) {
+        let Some(path) = history_file_path() else {
+            return (
+                Self::empty(),
+                Some(anyhow!("无法确定历史记录的保存位置，本次的记录不会保存")),
+            );
+        };
+
+        if !path.exists() {
+            return (Self::new(path, vec![]), None);
+        }
+
+        match read_records(&path) {
+            Ok(records) => (Self::new(path, records), None),
+            Err(err) => match backup(&path) {
+                Ok(backup) => (
+                    Self::new(path, vec![]),
+                    Some(err.context(format!("原文件已备份为：{}", backup.display()))),
+                ),
+                Err(backup_err) => (
+                    Self::empty(),
+                    Some(err.context(format!("{backup_err:#}，本次的记录不会保存"))),
+                ),
+            },
+        }
+    }
+
+    fn new(path: PathBuf, records: Vec<HistoryRecord>) -> Self {
+        Self {
+            path: Some(path),
+            records,
+        }
+    }
+
+    /// 空的历史记录，不会写入磁盘
+    pub fn empty() -> Self {
+        Self {
+            path: None,
+            records: vec![],
+        }
+    }
+
+    /// 按时间从新到旧返回所有记录
+    pub fn records(&self) -> impl Iterator<Item = &HistoryRecord> {
+        self.records.iter().rev()
+    }
+
+    /// 添加一条记录并保存
+    pub fn add(&mut self, result: GuaResult) -> Result<()> {
+        let id = self
+            .records
+            .iter()
+            .map(|r| r.id)
+            .max()
+            .map_or(1, |id| id + 1);
+
+        self.records.push(HistoryRecord { id, result });
+
+        self.save()
+    }
+
+    /// 删除指定编号的记录并保存
+    pub fn remove(&mut self, id: u64) -> Result<()> {
+        self.records.retain(|r| r.id != id);
+
+        self.save()
+    }
+
+    /// 写入本地文件
+    fn save(&self) -> Result<()> {
+        let path = self
+            .path
+            .as_ref()
+            .ok_or_else(|| anyhow!("无法确定历史记录的保存位置"))?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("无法创建目录：{}", dir.display()))?;
+        }
+
+        let content = serde_json::to_string_pretty(&self.records)?;
+
+        fs::write(path, content)
+            .with_context(|| format!("无法写入历史记录文件：{}", path.display()))
+    }
+}
+
+/// 读取并解析历史记录文件
+fn read_records(path: &Path) -> Result<Vec<HistoryRecord>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("无法读取历史记录文件：{}", path.display()))?;
+
+    serde_json::from_str(&content)
+        .with_context(|| format!("无法解析历史记录文件：{}", path.display()))
+}
+
+/// 将无法加载的历史记录文件改名备份，返回备份的路径
+///
+/// 优先使用 `history.json.bak`，已存在时依次加上序号
+fn backup(path: &Path) -> Result<PathBuf> {
+    let backup = (0..)
+        .map(|index| match index {
+            0 => path.with_extension("json.bak"),
+            index => path.with_extension(format!("json.bak.{index}")),
+        })
+        .find(|backup| !backup.exists())
+        .ok_or_else(|| anyhow!("无法备份历史记录文件：{}", path.display()))?;
+
+    fs::rename(path, &backup)
+        .with_context(|| format!("无法备份历史记录文件：{}", path.display()))?;
+
+    Ok(backup)
+}
+
+/// 历史记录文件路径
+///
+/// 位于系统的应用数据目录下
+fn history_file_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("gua").join(HISTORY_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{backup, read_records};
+
+    #[test]
+    /// 无法解析的文件会被改名备份，已有的备份不会被覆盖
+    fn test_backup() {
+        let dir = std::env::temp_dir().join(format!("gua-history-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let path = dir.join("history.json");
+
+        fs::write(&path, "旧的记录").unwrap();
+        assert!(read_records(&path).is_err());
+        assert_eq!(backup(&path).unwrap(), dir.join("history.json.bak"));
+        assert!(!path.exists());
+
+        fs::write(&path, "又一份旧的记录").unwrap();
+        assert_eq!(backup(&path).unwrap(), dir.join("history.json.bak.1"));
+        assert_eq!(
+            fs::read_to_string(dir.join("history.json.bak")).unwrap(),
+            "旧的记录"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,3 +1,4 @@
+pub mod gua_xiang;
 pub mod header;
 pub mod home;
 pub mod sidebar;
@@ -0,0 +1,168 @@
+use gpui::{
+    App, Axis, Div, Hsla, IntoElement, ParentElement, Pixels, RenderOnce, SharedString, Styled,
+    Window, div, prelude::FluentBuilder, px,
+};
+use gpui_component::{ActiveTheme, h_flex, v_flex};
+
+use crate::core::models::{Gua8, Gua64, Gua64YaoIndex, Yao};
+
+/// 卦象图形
+///
+/// 用实线和断线画出八卦或六十四卦，也可以只显示 Unicode 卦符
+///
+/// # 注意
+/// 爻的顺序是从下到上的，竖直方向时初爻在最下面，水平方向时初爻在最左边
+#[derive(IntoElement)]
+pub struct GuaXiang {
+    /// 所有爻，从下往上
+    yao_list: Vec<Yao>,
+    /// 需要高亮的爻（动爻），从下往上，初爻为 0，使用警示色
+    highlight: Vec<usize>,
+    /// 爻的长度
+    size: Pixels,
+    /// 爻的颜色，默认使用前景色
+    color: Option<Hsla>,
+    /// 方向
+    orientation: Axis,
+    /// 是否只显示 Unicode 卦符
+    symbol: bool,
+}
+
+impl GuaXiang {
+    /// 根据爻创建，爻的顺序从下往上
+    pub fn new(yao_list: impl IntoIterator<Item = Yao>) -> Self {
+        Self {
+            yao_list: yao_list.into_iter().collect(),
+            highlight: vec![],
+            size: px(64.),
+            color: None,
+            orientation: Axis::Vertical,
+            symbol: false,
+        }
+    }
+
+    /// 六十四卦
+    pub fn gua64(gua: &Gua64) -> Self {
+        Self::new(gua.clone())
+    }
+
+    /// 八卦
+    pub fn gua8(gua: Gua8) -> Self {
+        Self::new([gua.first_yao, gua.second_yao, gua.third_yao])
+    }
+
+    /// 高亮指定的爻，并标记 `○`（老阳）或 `×`（老阴）
+    pub fn highlight(mut self, indexes: &[Gua64YaoIndex]) -> Self {
+        self.highlight = indexes.iter().map(|index| *index as usize - 1).collect();
+        self
+    }
+
+    /// 爻的长度，粗细和间距按比例计算
+    pub fn size(mut self, size: Pixels) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// 爻的颜色
+    pub fn color(mut self, color: Hsla) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// 方向，默认竖直排列
+    pub fn orientation(mut self, orientation: Axis) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// 只显示 Unicode 卦符
+    pub fn symbol(mut self, symbol: bool) -> Self {
+        self.symbol = symbol;
+        self
+    }
+
+    /// Unicode 卦符，爻数不是三或六时为空
+    fn symbol_text(&self) -> SharedString {
+        match *self.yao_list.as_slice() {
+            [first, second, third] => Gua8::new(first, second, third).symbol().into(),
+            [first, second, third, fourth, fifth, sixth] => Gua64::new(
+                Gua8::new(fourth, fifth, sixth),
+                Gua8::new(first, second, third),
+            )
+            .symbol()
+            .to_string()
+            .into(),
+            _ => SharedString::default(),
+        }
+    }
+
+    /// 单个爻
+    fn yao_line(&self, yao: Yao, color: Hsla) -> Div {
+        let thickness = self.size * 0.125;
+
+        let line = match self.orientation {
+            Axis::Vertical => h_flex().w(self.size).h(thickness),
+            Axis::Horizontal => v_flex().h(self.size).w(thickness),
+        };
+
+        match yao.is_yang() {
+            true => line.child(div().size_full().bg(color)),
+            false => line
+                .gap(thickness * 2.)
+                .child(div().flex_1().size_full().bg(color))
+                .child(div().flex_1().size_full().bg(color)),
+        }
+    }
+}
+
+impl RenderOnce for GuaXiang {
+    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
+        if self.symbol {
+            return div()
+                .text_size(self.size)
+                .line_height(self.size)
+                .when_some(self.color, |this, color| this.text_color(color))
+                .child(self.symbol_text());
+        }
+
+        let color = self.color.unwrap_or(cx.theme().foreground);
+        let highlight_color = cx.theme().danger;
+        let gap = self.size * 0.125;
+
+        let lines = self.yao_list.iter().enumerate().map(|(index, yao)| {
+            let is_highlight = self.highlight.contains(&index);
+
+            let mark = match (is_highlight, yao.is_yang()) {
+                (false, _) => "",
+                (true, true) => "○",
+                (true, false) => "×",
+            };
+
+            let color = if is_highlight { highlight_color } else { color };
+
+            let mark = div()
+                .text_size(self.size * 0.25)
+                .text_color(color)
+                .child(mark);
+
+            match self.orientation {
+                Axis::Vertical => h_flex()
+                    .gap(gap)
+                    .child(self.yao_line(*yao, color))
+                    .child(mark.w(self.size * 0.25)),
+                Axis::Horizontal => v_flex()
+                    .gap(gap)
+                    .child(mark.h(self.size * 0.25))
+                    .child(self.yao_line(*yao, color)),
+            }
+        });
+
+        match self.orientation {
+            // 竖直方向从上爻往下画
+            Axis::Vertical => v_flex()
+                .gap(gap)
+                .children(lines.collect::<Vec<_>>().into_iter().rev()),
+            Axis::Horizontal => h_flex().gap(gap).children(lines),
+        }
+    }
+}
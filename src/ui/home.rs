@@ -1,10 +1,17 @@
 use gpui::{prelude::FluentBuilder, *};
-use gpui_component::{h_flex, v_flex};
+use gpui_component::{
+    ActiveTheme,
+    button::{Button, ButtonVariants},
+    h_flex, v_flex,
+};
 
-use crate::ui::{
-    header::AppHeader,
-    sidebar::{AppSideBar, StageItem},
-    stage::{about::About, history::History, library::Library, qi_gua::QiGua},
+use crate::{
+    state::global::GlobalState,
+    ui::{
+        header::AppHeader,
+        sidebar::{AppSideBar, StageItem},
+        stage::{about::About, ba_gua::BaGua, history::History, library::Library, qi_gua::QiGua},
+    },
 };
 
 pub struct HomeWindow {
@@ -22,11 +29,12 @@ impl HomeWindow {
         let stages = vec![
             StageContainer::view::<QiGua>(window, cx),
             StageContainer::view::<Library>(window, cx),
+            StageContainer::view::<BaGua>(window, cx),
             StageContainer::view::<History>(window, cx),
             StageContainer::view::<About>(window, cx),
         ];
 
-        let sidebar = AppSideBar::view(window, cx, StageItem::QiGua);
+        let sidebar = AppSideBar::view(window, cx);
         let header = AppHeader::view(window, cx);
 
         Self {
@@ -35,11 +43,35 @@ impl HomeWindow {
             header,
         }
     }
+
+    /// 加载64卦信息失败时的提示，关闭后不再显示
+    fn gua64_info_error_banner(&self, error: String, cx: &mut Context<Self>) -> Div {
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .text_color(cx.theme().danger)
+            .child(error)
+            .child(
+                Button::new("dismiss-gua64-info-error")
+                    .label("知道了")
+                    .ghost()
+                    .on_click(cx.listener(|_, _, _, cx| {
+                        GlobalState::dismiss_gua64_info_error(cx);
+
+                        cx.notify();
+                    })),
+            )
+    }
 }
 
 impl Render for HomeWindow {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let active_stage = self.sidebar.read(cx).active_stage.clone();
+        let active_stage = GlobalState::state(cx).active_stage.clone();
+        let gua64_info_error = GlobalState::state(cx).gua64_info_error.clone();
 
         let active_stages: Vec<_> = self
             .stages
@@ -53,18 +85,24 @@ impl Render for HomeWindow {
 
         let active_stage = active_stages.get(0).unwrap();
 
-        v_flex().h_full().child(self.header.clone()).child(
-            h_flex()
-                .flex_1()
-                .child(div().h_full().child(self.sidebar.clone()))
-                .child(
-                    div()
-                        .flex_1()
-                        .h_full()
-                        .child(active_stage.clone())
-                        .into_any_element(),
-                ),
-        )
+        v_flex()
+            .h_full()
+            .child(self.header.clone())
+            .when_some(gua64_info_error, |this, error| {
+                this.child(self.gua64_info_error_banner(error, cx))
+            })
+            .child(
+                h_flex()
+                    .flex_1()
+                    .child(div().h_full().child(self.sidebar.clone()))
+                    .child(
+                        div()
+                            .flex_1()
+                            .h_full()
+                            .child(active_stage.clone())
+                            .into_any_element(),
+                    ),
+            )
     }
 }
 
@@ -1,35 +1,45 @@
 use gpui::{App, AppContext, Context, Entity, IntoElement, Render, Window, prelude::FluentBuilder};
 use gpui_component::{Icon, IconName, Side, sidebar::*};
 
+use crate::state::global::GlobalState;
+
 #[derive(PartialEq, Eq, Clone)]
 pub enum StageItem {
     QiGua,
     History,
     Library,
+    BaGua,
     About,
 }
 
 /// 应用侧边栏
+///
+/// 当前页面保存在 `GlobalState` 中，其他页面也可以切换
 pub struct AppSideBar {
     collapsed: bool,
-    pub active_stage: StageItem,
 }
 
 impl AppSideBar {
-    pub fn view(window: &mut Window, cx: &mut App, active_stage: StageItem) -> Entity<Self> {
-        cx.new(|cx| Self::new(window, cx, active_stage))
+    pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
+        cx.new(|cx| Self::new(window, cx))
     }
 
-    fn new(_: &mut Window, _: &mut Context<Self>, active_stage: StageItem) -> Self {
-        Self {
-            collapsed: false,
-            active_stage,
-        }
+    fn new(_: &mut Window, _: &mut Context<Self>) -> Self {
+        Self { collapsed: false }
+    }
+
+    /// 切换页面
+    fn set_active_stage(&mut self, stage: StageItem, cx: &mut Context<Self>) {
+        GlobalState::state_mut(cx).active_stage = stage;
+
+        cx.notify();
     }
 }
 
 impl Render for AppSideBar {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let active_stage = GlobalState::state(cx).active_stage.clone();
+
         Sidebar::new(Side::Left)
             .collapsed(self.collapsed)
             .collapsible(true)
@@ -39,25 +49,33 @@ impl Render for AppSideBar {
                         .child(
                             SidebarMenuItem::new("起卦")
                                 .icon(Icon::empty().path("icons/pencil-line.svg"))
-                                .active(self.active_stage == StageItem::QiGua)
-                                .on_click(cx.listener(|this, _, _, _| {
-                                    this.active_stage = StageItem::QiGua;
+                                .active(active_stage == StageItem::QiGua)
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.set_active_stage(StageItem::QiGua, cx);
                                 })),
                         )
                         .child(
                             SidebarMenuItem::new("历史")
                                 .icon(Icon::empty().path("icons/history.svg"))
-                                .active(self.active_stage == StageItem::History)
-                                .on_click(cx.listener(|this, _, _, _| {
-                                    this.active_stage = StageItem::History;
+                                .active(active_stage == StageItem::History)
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.set_active_stage(StageItem::History, cx);
                                 })),
                         )
                         .child(
                             SidebarMenuItem::new("资源")
                                 .icon(Icon::empty().path("icons/library.svg"))
-                                .active(self.active_stage == StageItem::Library)
-                                .on_click(cx.listener(|this, _, _, _| {
-                                    this.active_stage = StageItem::Library;
+                                .active(active_stage == StageItem::Library)
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.set_active_stage(StageItem::Library, cx);
+                                })),
+                        )
+                        .child(
+                            SidebarMenuItem::new("八卦")
+                                .icon(Icon::empty().path("icons/library.svg"))
+                                .active(active_stage == StageItem::BaGua)
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.set_active_stage(StageItem::BaGua, cx);
                                 })),
                         ),
                 ),
@@ -65,9 +83,9 @@ impl Render for AppSideBar {
                     SidebarMenu::new().child(
                         SidebarMenuItem::new("关于")
                             .icon(Icon::empty().path("icons/info.svg"))
-                            .active(self.active_stage == StageItem::About)
-                            .on_click(cx.listener(|this, _, _, _| {
-                                this.active_stage = StageItem::About;
+                            .active(active_stage == StageItem::About)
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.set_active_stage(StageItem::About, cx);
                             })),
                     ),
                 ),
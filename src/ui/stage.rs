@@ -1,4 +1,5 @@
 pub mod about;
+pub mod ba_gua;
 pub mod history;
 pub mod library;
 pub mod qi_gua;
@@ -0,0 +1,115 @@
+use gpui::*;
+use gpui_component::{ActiveTheme, StyledExt, h_flex, v_flex};
+
+use crate::{
+    core::models::Gua8,
+    ui::{gua_xiang::GuaXiang, home::Stage, sidebar::StageItem},
+};
+
+/// 表头
+const HEADERS: [&str; 13] = [
+    "卦象",
+    "卦名",
+    "自然",
+    "五行",
+    "先天数",
+    "后天数",
+    "先天方位",
+    "后天方位",
+    "家人",
+    "身体",
+    "动物",
+    "颜色",
+    "卦德",
+];
+
+/// 八卦类象速查
+pub struct BaGua {}
+
+impl BaGua {
+    pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
+        cx.new(|cx| Self::new(window, cx))
+    }
+
+    fn new(_: &mut Window, _: &mut Context<Self>) -> Self {
+        Self {}
+    }
+
+    /// 一行，每列等宽
+    fn row(&self, cells: Vec<AnyElement>, cx: &mut Context<Self>) -> Div {
+        h_flex()
+            .py_1()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .children(
+                cells
+                    .into_iter()
+                    .map(|cell| div().flex_1().flex().justify_center().child(cell)),
+            )
+    }
+
+    /// 一个八卦的类象
+    fn gua_row(&self, gua: Gua8, cx: &mut Context<Self>) -> Div {
+        let texts = [
+            format!("{} {}", gua.name(), gua.symbol()),
+            gua.zi_ran().to_string(),
+            gua.wu_xing().to_string(),
+            gua.xian_tian_number().to_string(),
+            gua.hou_tian_number().to_string(),
+            gua.xian_tian_fang_wei().to_string(),
+            gua.hou_tian_fang_wei().to_string(),
+            gua.jia_ren().to_string(),
+            gua.shen_ti().to_string(),
+            gua.dong_wu().to_string(),
+            gua.yan_se().to_string(),
+            gua.gua_de().to_string(),
+        ];
+
+        // 表格行较矮，卦画横放，初爻在左
+        let mut cells = vec![
+            GuaXiang::gua8(gua)
+                .size(px(24.))
+                .orientation(Axis::Horizontal)
+                .into_any_element(),
+        ];
+        cells.extend(texts.into_iter().map(|text| text.into_any_element()));
+
+        self.row(cells, cx)
+    }
+}
+
+impl Render for BaGua {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let headers = HEADERS
+            .into_iter()
+            .map(|header| div().font_semibold().child(header).into_any_element())
+            .collect();
+
+        v_flex()
+            .p_2()
+            .gap_2()
+            .child(div().child("八卦类象").text_2xl())
+            .child(
+                div()
+                    .text_color(cx.theme().muted_foreground)
+                    .child("按先天数排列，颜色取梅花易数，其余出自《说卦传》"),
+            )
+            .child(self.row(headers, cx))
+            .children(
+                Gua8::ALL
+                    .into_iter()
+                    .map(|gua| self.gua_row(gua, cx))
+                    .collect::<Vec<_>>(),
+            )
+    }
+}
+
+impl Stage for BaGua {
+    fn new_view(window: &mut Window, cx: &mut App) -> Entity<impl Render> {
+        Self::view(window, cx)
+    }
+
+    fn get_id() -> StageItem {
+        StageItem::BaGua
+    }
+}
@@ -1,6 +1,14 @@
 use gpui::*;
+use gpui_component::{
+    ActiveTheme, StyledExt,
+    button::{Button, ButtonVariants},
+    h_flex, v_flex,
+};
 
-use crate::ui::{home::Stage, sidebar::StageItem};
+use crate::{
+    state::{global::GlobalState, history::HistoryRecord},
+    ui::{home::Stage, sidebar::StageItem},
+};
 
 /// 历史记录
 pub struct History {}
@@ -20,11 +28,84 @@ impl History {
             .child(div().child("卜卦记录").text_2xl())
             .child(div().child("查看您的所有卜卦历史记录"))
     }
+
+    /// 单条记录
+    fn record_item(&self, record: &HistoryRecord, cx: &mut Context<Self>) -> Div {
+        let id = record.id;
+        let result = &record.result;
+
+        let date = result.date.format("%Y-%m-%d %H:%M:%S").to_string();
+        let bian_gua = match result.bian_gua() {
+            None => "无",
+            Some(gua) => gua.name(),
+        };
+
+        v_flex()
+            .p_2()
+            .gap_1()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_md()
+            .child(
+                h_flex()
+                    .justify_between()
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(div().font_semibold().child(result.source.name()))
+                            .child(div().text_color(cx.theme().muted_foreground).child(date)),
+                    )
+                    .child(
+                        Button::new(("delete-history", id))
+                            .label("删除")
+                            .ghost()
+                            .on_click(cx.listener(move |_, _, _, cx| {
+                                GlobalState::remove_history(cx, id);
+
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .child(format!("输入：{}", result.source.display()))
+            .child(format!(
+                "本卦：{}，动爻：{}，变卦：{}，互卦：{}",
+                result.ben_gua.name(),
+                result.dong_yao_names(),
+                bian_gua,
+                result.hu_gua.name()
+            ))
+    }
 }
 
 impl Render for History {
-    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
-        div().child(self.title()).child("暂未实现")
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let state = GlobalState::state(cx);
+        let records: Vec<HistoryRecord> = state.history.records().cloned().collect();
+        let errors: Vec<String> = [&state.history_load_error, &state.history_save_error]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect();
+
+        let content = if records.is_empty() {
+            div().child("还没有卜卦记录").into_any_element()
+        } else {
+            v_flex()
+                .gap_2()
+                .children(records.iter().map(|record| self.record_item(record, cx)))
+                .into_any_element()
+        };
+
+        v_flex()
+            .p_2()
+            .gap_3()
+            .child(self.title())
+            .children(
+                errors
+                    .into_iter()
+                    .map(|error| div().text_color(cx.theme().danger).child(error)),
+            )
+            .child(content)
     }
 }
 
@@ -1,17 +1,24 @@
-use gpui::*;
-use gpui_component::button::Button;
+use gpui::{prelude::FluentBuilder, *};
+use gpui_component::{
+    ActiveTheme, StyledExt,
+    button::{Button, ButtonVariants},
+    h_flex, v_flex,
+};
 use rand::Rng;
 
 use crate::{
-    core::basic::Gua64Info,
+    core::{
+        basic::Gua64Info,
+        models::{BA_GONG_LIST, BaGong, GongShi, Gua8, Gua64},
+    },
     state::global::GlobalState,
-    ui::{home::Stage, sidebar::StageItem},
+    ui::{gua_xiang::GuaXiang, home::Stage, sidebar::StageItem},
 };
 
 /// 查询基础内容
 pub struct Library {
-    /// 当前卦象的索引
-    gua_index: usize,
+    /// 当前卦象的文王卦序，从 1 到 64
+    king_wen_number: u8,
     /// 六十四卦信息列表
     gua64: Vec<Gua64Info>,
 }
@@ -25,44 +32,161 @@ impl Library {
         let gua64 = GlobalState::state(cx).gua64_info_list.clone();
 
         Self {
-            gua_index: 0,
+            king_wen_number: 1,
             gua64,
         }
     }
 
+    /// 当前展示的卦象
+    fn gua(&self) -> Gua64 {
+        Gua64::from_king_wen_number(self.king_wen_number).unwrap_or(Gua64::乾)
+    }
+
     /// 随机展示一个卦象
     fn change_random(&mut self) {
-        let mut random_index = rand::rng().random_range(0..self.gua64.len());
+        let mut random_number = rand::rng().random_range(1..=64);
 
-        while random_index == self.gua_index {
-            random_index = rand::rng().random_range(0..self.gua64.len());
+        while random_number == self.king_wen_number {
+            random_number = rand::rng().random_range(1..=64);
         }
 
-        self.gua_index = random_index;
+        self.king_wen_number = random_number;
+    }
+
+    /// 按文王卦序切换到上一卦或下一卦，首尾相接
+    fn change_by_step(&mut self, step: i8) {
+        let index = (self.king_wen_number as i8 - 1 + step).rem_euclid(64);
+
+        self.king_wen_number = index as u8 + 1;
+    }
+
+    /// 上下卦的类象，如 `上卦：坎（水，五行属水，卦德为陷）`
+    fn gua8_text(&self, label: &str, gua: Gua8) -> String {
+        format!(
+            "{label}：{}（{}，五行属{}，卦德为{}）",
+            gua.name(),
+            gua.zi_ran(),
+            gua.wu_xing(),
+            gua.gua_de()
+        )
+    }
+
+    /// 按京房八宫浏览
+    ///
+    /// 第一行切换宫，第二行列出当前宫的八个卦
+    fn ba_gong_content(&self, gua: &Gua64, cx: &mut Context<Self>) -> Div {
+        let ba_gong = gua.ba_gong();
+
+        let gong_buttons = BA_GONG_LIST.into_iter().enumerate().map(|(index, gong)| {
+            let target = BaGong::new(gong, GongShi::本宫).gua().king_wen_number();
+
+            Button::new(("ba-gong", index))
+                .label(format!("{}宫", gong.name()))
+                .when(gong == ba_gong.gong, |this| this.primary())
+                .when(gong != ba_gong.gong, |this| this.ghost())
+                .on_click(cx.listener(move |this, _, _, _| {
+                    this.king_wen_number = target;
+                }))
+        });
+
+        let gua_buttons = BaGong::gong_list(ba_gong.gong)
+            .into_iter()
+            .zip(GongShi::ALL)
+            .enumerate()
+            .map(|(index, (item, shi))| {
+                let target = item.king_wen_number();
+
+                Button::new(("gong-gua", index))
+                    .label(format!("{} {}", shi.name(), item.name()))
+                    .when(item == *gua, |this| this.primary())
+                    .when(item != *gua, |this| this.outline())
+                    .on_click(cx.listener(move |this, _, _, _| {
+                        this.king_wen_number = target;
+                    }))
+            });
+
+        v_flex()
+            .gap_1()
+            .child(h_flex().gap_1().children(gong_buttons.collect::<Vec<_>>()))
+            .child(h_flex().gap_1().children(gua_buttons.collect::<Vec<_>>()))
+            .child(format!(
+                "{}{}，世爻：{}，应爻：{}",
+                ba_gong.name(),
+                ba_gong.shi.name(),
+                gua.shi_yao().name(),
+                gua.ying_yao().name()
+            ))
     }
 }
 
 impl Render for Library {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // 从其他页面跳转过来时，展示指定的卦象
+        if let Some(gua) = GlobalState::state_mut(cx).library_gua.take() {
+            self.king_wen_number = gua.king_wen_number();
+        }
+
+        let gua = self.gua();
+
         div()
             .flex()
             .flex_col()
             .p_2()
             .child(
-                div().flex().gap_1().child(
-                    Button::new("random-gua")
-                        .outline()
-                        .label("随机来一卦")
-                        .on_click(cx.listener(|this, _, _, _| {
-                            this.change_random();
-                        })),
-                ),
+                div()
+                    .flex()
+                    .gap_1()
+                    .child(
+                        Button::new("prev-gua")
+                            .outline()
+                            .label("上一卦")
+                            .on_click(cx.listener(|this, _, _, _| {
+                                this.change_by_step(-1);
+                            })),
+                    )
+                    .child(
+                        Button::new("next-gua")
+                            .outline()
+                            .label("下一卦")
+                            .on_click(cx.listener(|this, _, _, _| {
+                                this.change_by_step(1);
+                            })),
+                    )
+                    .child(
+                        Button::new("random-gua")
+                            .outline()
+                            .label("随机来一卦")
+                            .on_click(cx.listener(|this, _, _, _| {
+                                this.change_random();
+                            })),
+                    ),
             )
             .gap_3()
-            .child({
-                let gua_info = self.gua64.get(self.gua_index).unwrap().clone();
-
-                cx.new(|_| gua_info)
+            .child(self.ba_gong_content(&gua, cx))
+            .child(
+                h_flex()
+                    .gap_4()
+                    .child(GuaXiang::gua64(&gua))
+                    .child(GuaXiang::gua64(&gua).symbol(true))
+                    .child(
+                        v_flex()
+                            .child(div().text_xl().font_semibold().child(gua.full_name()))
+                            .child(format!(
+                                "文王卦序：第 {} 卦，伏羲卦序：{}",
+                                gua.king_wen_number(),
+                                gua.fu_xi_number()
+                            ))
+                            .child(self.gua8_text("上卦", gua.shang()))
+                            .child(self.gua8_text("下卦", gua.xia())),
+                    ),
+            )
+            .map(|this| match Gua64Info::find(&self.gua64, &gua).cloned() {
+                Some(gua_info) => this.child(cx.new(|_| gua_info)),
+                None => this.child(
+                    div()
+                        .text_color(cx.theme().danger)
+                        .child("六十四卦资料加载失败，无法显示卦辞"),
+                ),
             })
     }
 }
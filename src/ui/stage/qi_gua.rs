@@ -2,7 +2,10 @@ use gpui::*;
 use gpui_component::{h_flex, v_flex};
 
 use crate::{
-    qigua::{core::QiGuaCore, liu_yao::LiuYao, time::Time, two_number::TwoNumber},
+    qigua::{
+        core::QiGuaCore, da_yan::DaYanQiGua, liu_yao::LiuYao, time::Time, two_number::TwoNumber,
+        yao_gua::YaoGua,
+    },
     ui::{home::Stage, sidebar::StageItem, stage::result::ResultView},
 };
 
@@ -11,6 +14,8 @@ pub struct QiGua {
     two_number: Entity<TwoNumber>,
     time: Entity<Time>,
     liu_yao: Entity<LiuYao>,
+    da_yan: Entity<DaYanQiGua>,
+    yao_gua: Entity<YaoGua>,
     result: Entity<ResultView>,
 }
 
@@ -23,6 +28,8 @@ impl QiGua {
         let two_number = TwoNumber::view(window, cx);
         let liu_yao = LiuYao::view(window, cx);
         let time = Time::view(window, cx);
+        let da_yan = DaYanQiGua::view(window, cx);
+        let yao_gua = YaoGua::view(window, cx);
         let result = ResultView::view(window, cx);
 
         Self {
@@ -30,6 +37,8 @@ impl QiGua {
             result,
             time,
             liu_yao,
+            da_yan,
+            yao_gua,
         }
     }
 
@@ -49,10 +58,13 @@ impl Render for QiGua {
             .child(self.title())
             .child(
                 h_flex()
+                    .flex_wrap()
                     .gap_2()
                     .child(self.two_number.clone())
                     .child(self.time.clone())
-                    .child(self.liu_yao.clone()),
+                    .child(self.liu_yao.clone())
+                    .child(self.yao_gua.clone())
+                    .child(self.da_yan.clone()),
             )
             .child(self.result.clone())
     }
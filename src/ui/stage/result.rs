@@ -1,8 +1,22 @@
 use gpui::{
-    App, AppContext, Context, Entity, IntoElement, ParentElement, Render, Styled, Window, div,
+    App, AppContext, Context, Div, Entity, InteractiveElement, IntoElement, ParentElement, Render,
+    StatefulInteractiveElement, Styled, Window, div, prelude::FluentBuilder, px,
 };
+use gpui_component::{ActiveTheme, StyledExt, h_flex, v_flex};
 
-use crate::state::global::GlobalState;
+use crate::{
+    core::{
+        ba_gua::{GuaFamily, GuaResult, QiGuaSource},
+        basic::Gua64Info,
+        da_yan::DaYan,
+        mei_hua::{MeiHuaReading, ti_yong},
+        models::{Gua64, Gua64YaoIndex},
+        pai_pan::PaiPan,
+        zhu_xi::{ZhuXiReading, zhan_ci},
+    },
+    state::global::GlobalState,
+    ui::gua_xiang::GuaXiang,
+};
 
 /// 算卦结果
 pub struct ResultView;
@@ -14,6 +28,285 @@ impl ResultView {
     pub fn new(_: &mut Window, _: &mut App) -> Self {
         Self {}
     }
+
+    /// 起卦方式
+    fn source_content(&self, result: &GuaResult) -> impl IntoElement {
+        v_flex()
+            .child(format!("起卦方式：{}", result.source.name()))
+            .child(format!("原始输入：{}", result.source.display()))
+            .child(format!("动爻：{}", result.dong_yao_names()))
+    }
+
+    /// 衍生卦：互卦、错卦、综卦、交卦
+    ///
+    /// 点击后跳转到资源页查看详情
+    fn family_content(&self, family: GuaFamily, cx: &mut Context<Self>) -> Div {
+        h_flex()
+            .gap_3()
+            .children(
+                family
+                    .list()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, (name, gua))| {
+                        let target = gua.clone();
+
+                        v_flex()
+                            .id(("family", index))
+                            .items_center()
+                            .cursor_pointer()
+                            .on_click(move |_, window, cx| {
+                                cx.stop_propagation();
+
+                                GlobalState::open_library(cx, &target);
+
+                                window.refresh();
+                            })
+                            .child(
+                                GuaXiang::gua64(gua)
+                                    .size(px(24.))
+                                    .color(cx.theme().muted_foreground),
+                            )
+                            .child(
+                                div()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(format!("{name}：{}", gua.name())),
+                            )
+                    }),
+            )
+    }
+
+    /// 单个卦象：卦画以及卦辞、彖辞、大象
+    ///
+    /// 点击后跳转到资源页查看详情
+    fn gua_content(
+        &self,
+        label: &'static str,
+        gua: Gua64,
+        dong_yao: &[Gua64YaoIndex],
+        family: Option<GuaFamily>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let info = Gua64Info::find(&GlobalState::state(cx).gua64_info_list, &gua).cloned();
+
+        let target = gua.clone();
+
+        v_flex()
+            .id(label)
+            .flex_1()
+            .p_2()
+            .gap_2()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_md()
+            .cursor_pointer()
+            .on_click(move |_, window, cx| {
+                GlobalState::open_library(cx, &target);
+
+                window.refresh();
+            })
+            .child(
+                div()
+                    .font_semibold()
+                    .child(format!("{label}：{}", gua.full_name())),
+            )
+            .child(GuaXiang::gua64(&gua).highlight(dong_yao))
+            .when_some(family, |this, family| {
+                this.child(self.family_content(family, cx))
+            })
+            .when_some(info, |this, info| {
+                this.child(format!("卦辞：{}", info.gua_ci))
+                    .child(format!("彖辞：{}", info.tuan_ci))
+                    .child(format!("大象：{}", info.da_xiang))
+            })
+    }
+
+    /// 六爻排盘表格，从上爻往下排列
+    fn pai_pan_content(&self, pai_pan: PaiPan, cx: &mut Context<Self>) -> Div {
+        let header = format!(
+            "{}（{}），{}{}，月建：{}，日辰：{}，旬空：{}{}",
+            pai_pan.ba_gong.name(),
+            pai_pan.ba_gong.gong.wu_xing(),
+            pai_pan.ben_gua.name(),
+            pai_pan.ba_gong.shi.name(),
+            pai_pan.yue_jian,
+            pai_pan.ri_chen,
+            pai_pan.xun_kong[0],
+            pai_pan.xun_kong[1]
+        );
+
+        let cell = |width: f32| div().w(px(width)).flex_shrink_0();
+
+        let title = h_flex()
+            .gap_2()
+            .text_color(cx.theme().muted_foreground)
+            .child(cell(48.).child("六神"))
+            .child(cell(96.).child("本卦"))
+            .child(cell(48.))
+            .child(cell(32.).child("世应"))
+            .child(cell(96.).child("变爻"))
+            .child(cell(96.).child("伏神"))
+            .child(cell(160.).child("旺衰"));
+
+        let fu_shen_list = pai_pan.fu_shen;
+
+        let rows = pai_pan.yao_list.into_iter().rev().map(|yao| {
+            let shi_ying = match (yao.is_shi, yao.is_ying) {
+                (true, _) => "世",
+                (_, true) => "应",
+                _ => "",
+            };
+
+            let kong = if yao.is_kong { "（空）" } else { "" };
+
+            let bian = match yao.bian {
+                Some((na_jia, liu_qin)) => format!("{liu_qin}{}", na_jia.display()),
+                None => String::new(),
+            };
+
+            // 伏神伏于此爻之下，此爻即为飞神
+            let fu_shen = fu_shen_list
+                .iter()
+                .filter(|fu_shen| fu_shen.index == yao.index)
+                .map(|fu_shen| format!("{}{}", fu_shen.liu_qin, fu_shen.na_jia.display()))
+                .collect::<Vec<_>>()
+                .join("，");
+
+            let badges = yao.wang_shuai.badges().into_iter().map(|badge| {
+                div()
+                    .px_1()
+                    .text_xs()
+                    .rounded_sm()
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .when(badge.is_warning(), |this| {
+                        this.text_color(cx.theme().danger)
+                    })
+                    .child(badge.to_string())
+            });
+
+            h_flex()
+                .gap_2()
+                .when(yao.is_dong, |this| this.text_color(cx.theme().danger))
+                .child(cell(48.).child(yao.liu_shen.to_string()))
+                .child(cell(96.).child(format!("{}{}{kong}", yao.liu_qin, yao.na_jia.display())))
+                .child(
+                    cell(48.).child(
+                        GuaXiang::new([yao.yao])
+                            .size(px(32.))
+                            .when(yao.is_dong, |this| this.highlight(&[Gua64YaoIndex::First])),
+                    ),
+                )
+                .child(cell(32.).child(shi_ying))
+                .child(cell(96.).child(bian))
+                .child(cell(96.).child(fu_shen))
+                .child(
+                    cell(160.)
+                        .flex()
+                        .gap_1()
+                        .children(badges.collect::<Vec<_>>()),
+                )
+        });
+
+        v_flex()
+            .gap_1()
+            .child(div().font_semibold().child("六爻排盘"))
+            .child(div().text_color(cx.theme().muted_foreground).child(header))
+            .child(title)
+            .children(rows.collect::<Vec<_>>())
+            .children(fu_shen_list.iter().map(|fu_shen| {
+                div().text_color(cx.theme().muted_foreground).child(format!(
+                    "缺{}：伏神{}{}伏于{}飞神{}{}之下",
+                    fu_shen.liu_qin,
+                    fu_shen.liu_qin,
+                    fu_shen.na_jia.display(),
+                    fu_shen.index.name(),
+                    fu_shen.fei_liu_qin,
+                    fu_shen.fei_shen.display()
+                ))
+            }))
+    }
+
+    /// 朱熹断卦规则得出的文辞
+    fn zhan_ci_content(&self, reading: ZhuXiReading, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .gap_1()
+            .child(div().font_semibold().child("占辞（朱熹《易学启蒙》）"))
+            .child(
+                div()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(reading.rule),
+            )
+            .children(reading.ci_list.into_iter().map(|ci| {
+                let title = match ci.is_main {
+                    true => format!("{}（主）", ci.title),
+                    false => ci.title,
+                };
+
+                v_flex()
+                    .child(div().font_semibold().child(title))
+                    .child(ci.content)
+            }))
+    }
+
+    /// 梅花易数体用断卦
+    fn mei_hua_content(&self, reading: MeiHuaReading, cx: &mut Context<Self>) -> Div {
+        v_flex()
+            .gap_1()
+            .child(div().font_semibold().child("体用（梅花易数）"))
+            .child(reading.ti_display())
+            .children(reading.items.into_iter().map(|item| {
+                h_flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(item.label),
+                    )
+                    .child(format!(
+                        "{}（{}，{}）",
+                        item.gua.name(),
+                        item.gua.zi_ran(),
+                        item.gua.wu_xing()
+                    ))
+                    .child(format!("{}，{}", item.ti_yong, item.ti_yong.ji_xiong()))
+            }))
+    }
+
+    /// 大衍筮法每一爻的三变过程
+    fn da_yan_content(&self, da_yan: DaYan, cx: &mut Context<Self>) -> Div {
+        let yao_list = da_yan
+            .yao_list
+            .into_iter()
+            .zip(Gua64YaoIndex::ALL)
+            .rev()
+            .map(|(yao, index)| {
+                let bian_list = yao.bian_list.into_iter().enumerate().map(|(i, bian)| {
+                    h_flex()
+                        .gap_2()
+                        .child(
+                            div()
+                                .text_color(cx.theme().muted_foreground)
+                                .child(format!("第{}变", i + 1)),
+                        )
+                        .children(bian.steps())
+                });
+
+                v_flex()
+                    .child(div().font_semibold().child(format!(
+                        "{}：{}，{}",
+                        index.name(),
+                        yao.number(),
+                        yao.name()
+                    )))
+                    .children(bian_list.collect::<Vec<_>>())
+            });
+
+        v_flex()
+            .gap_1()
+            .child(div().font_semibold().child("揲蓍过程（从上爻到初爻）"))
+            .children(yao_list.collect::<Vec<_>>())
+    }
 }
 
 impl Render for ResultView {
@@ -22,7 +315,67 @@ impl Render for ResultView {
 
         match gua_result {
             None => div().child("还没有进行算卦！".to_string()),
-            Some(result) => div().p_2().child(cx.new(|_| result.clone())),
+            Some(result) => {
+                let reading = zhan_ci(&result, &GlobalState::state(cx).gua64_info_list);
+                let history_save_error = GlobalState::state(cx).history_save_error.clone();
+
+                // 变卦中的动爻已经变过，按变后的阴阳会标反 ○ 和 ×，所以只在本卦中标记
+                let bian_gua = match result.bian_gua() {
+                    Some(gua) => self
+                        .gua_content("变卦", gua, &[], result.bian_gua_family(), cx)
+                        .into_any_element(),
+                    None => div().flex_1().p_2().child("无变卦").into_any_element(),
+                };
+
+                div().p_2().child(
+                    v_flex()
+                        .gap_3()
+                        .when_some(history_save_error, |this, error| {
+                            this.child(div().text_color(cx.theme().danger).child(error))
+                        })
+                        .child(self.source_content(&result))
+                        .child(
+                            h_flex()
+                                .items_start()
+                                .gap_2()
+                                .child(self.gua_content(
+                                    "本卦",
+                                    result.ben_gua.clone(),
+                                    &result.dong_yao,
+                                    Some(result.ben_gua_family()),
+                                    cx,
+                                ))
+                                .child(bian_gua)
+                                .child(self.gua_content(
+                                    "互卦",
+                                    result.hu_gua.clone(),
+                                    &[],
+                                    None,
+                                    cx,
+                                )),
+                        )
+                        .when_some(
+                            ti_yong(&result).filter(|_| {
+                                matches!(
+                                    result.source,
+                                    QiGuaSource::TwoNumber { .. } | QiGuaSource::Time { .. }
+                                )
+                            }),
+                            |this, reading| this.child(self.mei_hua_content(reading, cx)),
+                        )
+                        .when(result.source.liu_yao_list().is_some(), |this| {
+                            this.child(self.pai_pan_content(PaiPan::new(&result), cx))
+                        })
+                        .when_some(
+                            match &result.source {
+                                QiGuaSource::DaYan(da_yan) => Some(*da_yan),
+                                _ => None,
+                            },
+                            |this, da_yan| this.child(self.da_yan_content(da_yan, cx)),
+                        )
+                        .child(self.zhan_ci_content(reading, cx)),
+                )
+            }
         }
     }
 }